@@ -0,0 +1,12 @@
+use rtrend::{AsyncClient, Country, Keywords, SearchInterest};
+
+#[tokio::main]
+async fn main() {
+    let keywords = Keywords::new(vec!["Cinema"]);
+    let country = Country::ALL;
+
+    let client = AsyncClient::new_async(keywords, country).await.build().await;
+
+    let search_interest = SearchInterest::new(client).get().await;
+    println!("{:#?}", search_interest);
+}
@@ -0,0 +1,21 @@
+use rtrend::{Client, Country, DailyTrends, Keywords};
+
+fn main() {
+    // Daily trends do not need keywords
+    let client = Client::new(Keywords::default(), Country::US);
+
+    let daily_trends = DailyTrends::new(client);
+    let today = daily_trends.get();
+
+    // Walk back to the previous days
+    let before = daily_trends
+        .with_date(today.end_date_for_next_request)
+        .get();
+
+    for day in today.days.iter().chain(&before.days) {
+        println!("{}", day.formatted_date);
+        for search in &day.trending_searches {
+            println!("  {} ({})", search.title.query, search.formatted_traffic);
+        }
+    }
+}
@@ -14,5 +14,5 @@ fn main() {
         .with_category(Category::FoodAndDrink)
         .build();
     let related_queries = RelatedQueries::new(client).get();
-    println!("{}", related_queries);
+    println!("{:#?}", related_queries);
 }
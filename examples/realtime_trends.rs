@@ -0,0 +1,19 @@
+use rtrend::realtime_trends::RealtimeCategory;
+use rtrend::{Client, Country, Keywords, RealtimeTrends};
+
+fn main() {
+    // Realtime trends do not need keywords
+    let client = Client::new(Keywords::default(), Country::US);
+
+    let realtime_trends = RealtimeTrends::new(client).with_category(RealtimeCategory::SciTech);
+    let response = realtime_trends.get();
+
+    for story in &response.stories {
+        println!("{} [{}]", story.title, story.id);
+    }
+
+    if let Some(story) = response.stories.first() {
+        let timeline = realtime_trends.story_timeline(&story.id);
+        println!("{:#?}", timeline.timeline_data);
+    }
+}
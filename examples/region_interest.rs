@@ -7,5 +7,5 @@ fn main() {
     let client = Client::new(keywords, country).build();
     
     let region_interest = RegionInterest::new(client).get();
-    println!("{}", region_interest);
+    println!("{:#?}", region_interest);
 }
@@ -7,5 +7,5 @@ fn main() {
     let client = Client::new(keywords, country).build();
 
     let search_interest = SearchInterest::new(client).get();
-    println!("{}", search_interest);
+    println!("{:#?}", search_interest);
 }
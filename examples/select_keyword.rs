@@ -7,5 +7,5 @@ fn main() {
     let client = Client::new(keywords, country).build();
     
     let region_interest_pinterest = RegionInterest::new(client).get_for("Pinterest");
-    println!("{}", region_interest_pinterest);
+    println!("{:#?}", region_interest_pinterest);
 }
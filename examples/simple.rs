@@ -8,5 +8,5 @@ fn main(){
     
     // Then select the data you want. The interest of your keywords filtered by region for example:
     let region_interest = RegionInterest::new(client).get();
-    println!("{}", region_interest);
+    println!("{:#?}", region_interest);
 }
\ No newline at end of file
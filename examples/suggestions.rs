@@ -0,0 +1,10 @@
+use rtrend::{Client, Country, Keywords, Lang, Suggestions};
+
+fn main() {
+    // Suggestions do not need keywords
+    let client = Client::new(Keywords::default(), Country::ALL).with_lang(Lang::EN);
+
+    for topic in Suggestions::new(client, "rust").get() {
+        println!("{} - {} ({})", topic.mid, topic.title, topic.kind);
+    }
+}
@@ -0,0 +1,356 @@
+//! Collect the settings of a client without touching the network.
+//!
+//! The session is only retrieved by [`ClientBuilder::build`], and the explore request is only sent by [`ClientBuilder::connect`].
+//! A builder given a cookie with [`ClientBuilder::with_cookie`] or a session with [`ClientBuilder::with_session`] never needs the network to build a client,
+//! which is handy in tests and while parsing a configuration.
+//! [`ClientBuilder::build_with`] plugs another [`Transport`] in, to test without network.
+
+use crate::errors::{Error, Result};
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::client;
+use crate::comparison_item;
+use crate::proxy::ProxyPool;
+use crate::transport::Transport;
+use crate::{
+    Category, Client, ComparisonItem, Cookie, Country, Keywords, Lang, Period, Property, RateLimiter,
+    RetryPolicy, Session, Timezone,
+};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Builder of a [`Client`].
+///
+/// By default, the settings are the ones of [`Client::new`], without proxy nor timeout.
+///
+/// # Example
+/// ```
+/// # use rtrend::{ClientBuilder, Cookie, Country, Keywords, Lang, Period};
+/// # use std::time::Duration;
+/// let client = ClientBuilder::new()
+///     .with_keywords(Keywords::new(vec!["rust", "go"]))
+///     .with_country(Country::FR)
+///     .with_lang(Lang::FR)
+///     .with_period(Period::NinetyDay)
+///     .with_timezone(chrono_tz::Europe::Paris)
+///     .with_proxy("http://localhost:3128")
+///     .with_timeout(Duration::from_secs(10))
+///     .with_cookie(Cookie { nid: "NID=511=abc".to_string() })
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(client.country, Country::FR);
+/// assert_eq!(client.time, "today 3-m");
+/// assert!(client.response.widgets.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientBuilder {
+    pub keywords: Keywords,
+    pub country: Country,
+    pub lang: Lang,
+    pub category: Category,
+    pub property: Property,
+    pub time: String,
+    pub comparison: Vec<ComparisonItem>,
+    pub timezone: Timezone,
+    pub proxy: Option<String>,
+    pub proxies: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub session: Option<Session>,
+    pub session_file: Option<PathBuf>,
+    pub retry: RetryPolicy,
+    pub rate_limiter: Option<RateLimiter>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            keywords: Keywords::default(),
+            country: Country::ALL,
+            lang: Lang::EN,
+            category: Category::All,
+            property: Property::Web,
+            time: Period::OneYear.to_string(),
+            comparison: Vec::new(),
+            timezone: Timezone::default(),
+            proxy: None,
+            proxies: Vec::new(),
+            timeout: None,
+            session: None,
+            session_file: None,
+            retry: RetryPolicy::default(),
+            rate_limiter: None,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Create a `ClientBuilder` instance, no request is sent.
+    ///
+    /// Returns a `ClientBuilder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the keywords, see [`Client::with_keywords`].
+    pub fn with_keywords(mut self, keywords: Keywords) -> Self {
+        self.keywords = keywords;
+        self.comparison.clear();
+        self
+    }
+
+    /// Set the comparison item by item, see [`Client::with_comparison`].
+    pub fn with_comparison(mut self, items: Vec<ComparisonItem>) -> Self {
+        let items = comparison_item::normalize(items);
+        self.keywords = Keywords {
+            keywords: items.iter().map(|item| item.keyword.clone()).collect(),
+        };
+        self.comparison = items;
+        self
+    }
+
+    /// Set the country, by default all the countries.
+    pub fn with_country(mut self, country: Country) -> Self {
+        self.country = country;
+        self
+    }
+
+    /// Set the langage of the response, see [`Client::with_lang`].
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Set the category, see [`Client::with_category`].
+    pub fn with_category(mut self, category: Category) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Set the property, see [`Client::with_property`].
+    pub fn with_property(mut self, property: Property) -> Self {
+        self.property = property;
+        self
+    }
+
+    /// Set the period, see [`Client::with_period`].
+    pub fn with_period(mut self, period: impl ToString) -> Self {
+        self.time = period.to_string();
+        self
+    }
+
+    /// Set the timezone, see [`Client::with_timezone`].
+    pub fn with_timezone(mut self, timezone: impl Into<Timezone>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Send every request, the cookie handshake included, through a proxy (`http://`, `https://` or `socks5://` url).
+    pub fn with_proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_string());
+        self
+    }
+
+    /// Rotate the requests over these proxies, see [`ClientBuilder::build_rotating`].
+    pub fn with_proxies<S: AsRef<str>>(mut self, proxies: &[S]) -> Self {
+        self.proxies = proxies.iter().map(|proxy| proxy.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Set a timeout for every request, the cookie handshake included.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Use this consent cookie instead of retrieving a new one.
+    pub fn with_cookie(mut self, cookie: Cookie) -> Self {
+        self.session = Some(cookie.into());
+        self
+    }
+
+    /// Use this session instead of retrieving a new one, see [`Session`].
+    pub fn with_session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Reuse the session saved in this file unless it has expired, a new session is then retrieved and saved in the file.
+    ///
+    /// The session is saved again after every refresh, see [`Session::save`].
+    pub fn with_session_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.session_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set how the requests rejected by Google are retried, see [`Client::with_retry`].
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Limit the pace of the requests, see [`Client::with_rate_limit`].
+    pub fn with_rate_limit(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Build the client, the session is retrieved unless it has been set or loaded from a file.
+    ///
+    /// The explore request is not sent, see [`ClientBuilder::connect`].
+    ///
+    /// Returns an error if more than 5 keywords are set, if a period is not a valid [`TimeRange`](crate::TimeRange),
+    /// if the proxy url is invalid or if the session can't be retrieved or saved.
+    pub fn build(self) -> Result<Client> {
+        self.check()?;
+
+        let http = self.blocking_http()?;
+        let session = match (&self.session, &self.session_file) {
+            (Some(session), _) => session.clone(),
+            (None, Some(path)) => Session::try_load_or_fetch(path, &http)?,
+            (None, None) => Session::try_fetch(&http)?,
+        };
+
+        Ok(self.into_client(http, session))
+    }
+
+    /// Build a client sending its requests through another transport, like [`Fixtures`](crate::transport::Fixtures).
+    ///
+    /// No request is sent, the session is the one set with [`ClientBuilder::with_session`] or an empty one,
+    /// the session file is ignored.
+    /// The proxy and the timeout are left to the transport.
+    ///
+    /// Returns an error if more than 5 keywords are set or if a period is not a valid [`TimeRange`](crate::TimeRange).
+    ///
+    /// The keywords are only needed by the explore request, a client without any serves the trends and the suggestions.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::{ClientBuilder, Suggestions};
+    /// # use rtrend::transport::Fixtures;
+    /// let fixtures = Fixtures::new().with("autocomplete/rust", r#")]}',{"default": {"topics": []}}"#);
+    /// let client = ClientBuilder::new().build_with(fixtures).unwrap();
+    ///
+    /// assert!(Suggestions::new(client.clone(), "rust").try_get().unwrap().is_empty());
+    /// assert!(client.try_build().is_err());
+    /// ```
+    pub fn build_with<T: Transport>(self, transport: T) -> Result<Client<T>> {
+        self.check()?;
+
+        let session = self.session.clone().unwrap_or_default();
+        Ok(self.into_client(transport, session))
+    }
+
+    /// Build a client rotating its requests over the proxies set with [`ClientBuilder::with_proxies`],
+    /// each proxy retrieving its own cookie on its first use, see [`ProxyPool`].
+    ///
+    /// No request is sent, the timeout applies to every proxy.
+    ///
+    /// Returns an error if more than 5 keywords are set, if a period is not a valid [`TimeRange`](crate::TimeRange)
+    /// if no proxy is set (`Error::NoProxyLeft`) or if a proxy url is invalid.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::{ClientBuilder, Keywords};
+    /// # use std::time::Duration;
+    /// let client = ClientBuilder::new()
+    ///     .with_keywords(Keywords::new(vec!["rust"]))
+    ///     .with_proxies(&["http://10.0.0.1:3128", "socks5://10.0.0.2:1080"])
+    ///     .with_timeout(Duration::from_secs(10))
+    ///     .build_rotating()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(client.client.proxies().len(), 2);
+    /// ```
+    pub fn build_rotating(self) -> Result<Client<ProxyPool>> {
+        self.check()?;
+        if self.proxies.is_empty() {
+            return Err(Error::NoProxyLeft);
+        }
+
+        let pool = ProxyPool::new(&self.proxies, self.timeout)?;
+        let session = self.session.clone().unwrap_or_default();
+        Ok(self.into_client(pool, session))
+    }
+
+    /// Build the client and send the explore request, see [`Client::try_build`].
+    ///
+    /// Returns an error if the client can't be built or if the explore request fails.
+    pub fn connect(self) -> Result<Client> {
+        self.build()?.try_build()
+    }
+
+    /// Build an asynchronous client, see [`ClientBuilder::build`].
+    ///
+    /// Like the blocking client, it sends the session with every request and refreshes it,
+    /// saving it again to the session file if one is set.
+    #[cfg(feature = "async")]
+    pub async fn build_async(self) -> Result<AsyncClient> {
+        self.check()?;
+
+        let http = self.async_http()?;
+        let session = match (&self.session, &self.session_file) {
+            (Some(session), _) => session.clone(),
+            (None, Some(path)) => Session::try_load_or_fetch_async(path, &http).await?,
+            (None, None) => Session::try_fetch_async(&http).await?,
+        };
+
+        Ok(self.into_client(http, session))
+    }
+
+    /// Build an asynchronous client and send the explore request, see [`ClientBuilder::connect`].
+    #[cfg(feature = "async")]
+    pub async fn connect_async(self) -> Result<AsyncClient> {
+        self.build_async().await?.try_build().await
+    }
+
+    // Fail before any request is sent, the explore request also needs a keyword
+    fn check(&self) -> Result<()> {
+        if self.keywords.iter().count() > 5 {
+            return Err(Error::KeywordMaxCapacity);
+        }
+        client::check_periods(&self.time, &self.comparison)
+    }
+
+    // The clients send the session with every request, so it can be refreshed
+    fn blocking_http(&self) -> Result<reqwest::blocking::Client> {
+        let mut builder = reqwest::blocking::ClientBuilder::new();
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(builder.build()?)
+    }
+
+    #[cfg(feature = "async")]
+    fn async_http(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::ClientBuilder::new();
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(builder.build()?)
+    }
+
+    fn into_client<C>(self, http: C, session: Session) -> Client<C> {
+        Client {
+            client: http,
+            session,
+            country: self.country,
+            keywords: self.keywords,
+            lang: self.lang,
+            property: self.property,
+            time: self.time,
+            category: self.category,
+            comparison: self.comparison,
+            timezone: self.timezone,
+            retry: self.retry,
+            rate_limiter: self.rate_limiter,
+            response: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Record the exchanges with Google Trend and replay them later.
+//!
+//! Available with the `cassette` feature.
+//! A cassette is a JSON file listing the requests sent through a [`Transport`] and the responses received.
+//! The widget tokens are redacted, in the requests and in the explore responses, the cookie is never recorded
+//! and the values of the `Set-Cookie` headers are redacted.
+//! Once recorded, a cassette replays the same responses for the same requests, without network.
+
+use crate::errors::{Error, Result};
+use crate::trace::{self, REDACTED};
+use crate::transport::{HttpResponse, Transport};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, SET_COOKIE};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Content of a cassette file.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Tape {
+	pub interactions: Vec<Interaction>,
+}
+
+/// A request and its response.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Interaction {
+	/// Url without its query.
+	pub url: String,
+	/// Query params, in order, with the token redacted.
+	/// The timezone offset (`tz`) is recorded but not matched, it changes with daylight saving time.
+	pub query: Vec<(String, String)>,
+	pub status: u16,
+	/// Response headers, in order, with the value of the `Set-Cookie` cookies redacted.
+	#[serde(default)]
+	pub headers: Vec<(String, String)>,
+	pub body: String,
+}
+
+impl Interaction {
+    fn request(url: &Url) -> (String, Vec<(String, String)>) {
+        let mut base = url.clone();
+        base.set_query(None);
+
+        let query = url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if key == "token" { REDACTED.into() } else { value };
+                (key.into_owned(), value.into_owned())
+            })
+            .collect();
+
+        (base.to_string(), query)
+    }
+
+    fn matches(&self, url: &str, query: &[(String, String)]) -> bool {
+        self.url == url && matched_params(&self.query).eq(matched_params(query))
+    }
+
+    // The attributes of a `Set-Cookie` header are kept, only the value of the cookie is redacted
+    fn record_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+                let value = match (name == SET_COOKIE, value.split_once('=')) {
+                    (true, Some((cookie, rest))) => {
+                        let attributes = rest.find(';').map_or("", |end| &rest[end..]);
+                        format!("{}={}{}", cookie, REDACTED, attributes)
+                    }
+                    _ => value.to_string(),
+                };
+                Some((name.to_string(), value))
+            })
+            .collect()
+    }
+
+    fn replay_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|error| Error::Cassette(error.to_string()))?;
+            let value =
+                HeaderValue::from_str(value).map_err(|error| Error::Cassette(error.to_string()))?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+// The timezone offset of the client changes with daylight saving time, it is left out
+fn matched_params(query: &[(String, String)]) -> impl Iterator<Item = &(String, String)> {
+    query.iter().filter(|(key, _)| key != "tz")
+}
+
+/// Transport recording the exchanges of another transport, or replaying a recorded cassette.
+///
+/// While recording, the cassette file is written after every exchange.
+/// While replaying, each recorded exchange is answered once, in order, a request which has not been recorded is an `Error::Cassette`.
+///
+/// # Example
+/// ```
+/// # use rtrend::cassette::Cassette;
+/// # use rtrend::transport::Fixtures;
+/// # use rtrend::{ClientBuilder, Keywords, SearchInterest};
+/// # use chrono::FixedOffset;
+/// let path = std::env::temp_dir().join("rtrend-search-interest.json");
+/// let fixtures = Fixtures::new()
+///     .with("explore", r#")]}'{"widgets": [{"id": "TIMESERIES", "token": "secret", "request": {}}]}"#)
+///     .with("widgetdata/multiline", r#")]}',{"default": {"timelineData": []}}"#);
+///
+/// // Record once, through the network in real life
+/// let recorder = Cassette::record(fixtures, &path);
+/// let client = ClientBuilder::new()
+///     .with_keywords(Keywords::new(vec!["rust"]))
+///     .build_with(recorder)
+///     .unwrap()
+///     .try_build()
+///     .unwrap();
+/// SearchInterest::new(client).try_get().unwrap();
+///
+/// assert!(!std::fs::read_to_string(&path).unwrap().contains("secret"));
+///
+/// // Replay forever, even once the offset of the timezone has changed
+/// let player = Cassette::replay(&path).unwrap();
+/// let client = ClientBuilder::new()
+///     .with_keywords(Keywords::new(vec!["rust"]))
+///     .with_timezone(FixedOffset::east_opt(2 * 3600).unwrap())
+///     .build_with(player)
+///     .unwrap()
+///     .try_build()
+///     .unwrap();
+/// let timeline = SearchInterest::new(client).try_get().unwrap();
+///
+/// assert!(timeline.timeline_data.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cassette<T = reqwest::blocking::Client> {
+    inner: Option<T>,
+    path: PathBuf,
+    tape: Arc<Mutex<Tape>>,
+}
+
+impl<T: Transport> Cassette<T> {
+    /// Record the exchanges of `inner` into the file at `path`, the file is overwritten.
+    ///
+    /// Returns a `Cassette` instance.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::cassette::Cassette;
+    /// # use rtrend::transport::{Fixtures, HttpResponse, Transport};
+    /// # use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER, SET_COOKIE};
+    /// # use reqwest::{StatusCode, Url};
+    /// let path = std::env::temp_dir().join("rtrend-headers.json");
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(RETRY_AFTER, HeaderValue::from_static("30"));
+    /// headers.insert(SET_COOKIE, HeaderValue::from_static("NID=secret; Path=/"));
+    /// let too_many = HttpResponse { status: StatusCode::TOO_MANY_REQUESTS, headers, ..HttpResponse::ok("") };
+    ///
+    /// let url = Url::parse("https://trends.google.com/trends/api/explore").unwrap();
+    /// let recorder = Cassette::record(Fixtures::new().with_response("explore", too_many), &path);
+    /// recorder.get(&url, &HeaderMap::new()).unwrap();
+    ///
+    /// let response = Cassette::replay(&path).unwrap().get(&url, &HeaderMap::new()).unwrap();
+    /// assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+    /// assert_eq!(response.headers[RETRY_AFTER], "30");
+    /// assert_eq!(response.headers[SET_COOKIE], "NID=REDACTED; Path=/");
+    /// ```
+    pub fn record(inner: T, path: impl AsRef<Path>) -> Self {
+        Self {
+            inner: Some(inner),
+            path: path.as_ref().to_path_buf(),
+            tape: Arc::default(),
+        }
+    }
+
+    /// Exchanges recorded or left to replay.
+    pub fn tape(&self) -> Tape {
+        self.tape.lock().unwrap().clone()
+    }
+
+    fn save(&self, tape: &Tape) -> Result<()> {
+        let content = serde_json::to_string_pretty(tape)?;
+        fs::write(&self.path, content).map_err(|error| {
+            Error::Cassette(format!("can't write {}: {}", self.path.display(), error))
+        })
+    }
+}
+
+impl Cassette {
+    /// Replay the cassette recorded in the file at `path`.
+    ///
+    /// Returns an `Error::Cassette` if the file can't be read.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let content = fs::read_to_string(&path).map_err(|error| {
+            Error::Cassette(format!("can't read {}: {}", path.display(), error))
+        })?;
+
+        Ok(Self {
+            inner: None,
+            path,
+            tape: Arc::new(Mutex::new(serde_json::from_str(&content)?)),
+        })
+    }
+}
+
+impl<T: Transport> Transport for Cassette<T> {
+    fn get(&self, url: &Url, headers: &HeaderMap) -> Result<HttpResponse> {
+        let (base, query) = Interaction::request(url);
+
+        match &self.inner {
+            Some(inner) => {
+                // The tape is not locked while the request is sent, the clones record concurrently
+                let response = inner.get(url, headers)?;
+                let mut tape = self.tape.lock().unwrap();
+                tape.interactions.push(Interaction {
+                    url: base,
+                    query,
+                    status: response.status.as_u16(),
+                    headers: Interaction::record_headers(&response.headers),
+                    body: trace::redact_tokens(&response.body),
+                });
+                self.save(&tape)?;
+                Ok(response)
+            }
+            None => {
+                let mut tape = self.tape.lock().unwrap();
+                let position = tape
+                    .interactions
+                    .iter()
+                    .position(|interaction| interaction.matches(&base, &query))
+                    .ok_or_else(|| Error::Cassette(format!("no recorded response for {}", base)))?;
+                let interaction = tape.interactions.remove(position);
+
+                Ok(HttpResponse {
+                    status: StatusCode::from_u16(interaction.status)
+                        .map_err(|error| Error::Cassette(error.to_string()))?,
+                    headers: interaction.replay_headers()?,
+                    body: interaction.body,
+                })
+            }
+        }
+    }
+}
@@ -1,23 +1,46 @@
 //! Client used to initialize everything needed by the Google Trend API.
 
-use crate::{utils, Category, Cookie, Country, Keywords, Lang, Period, Property};
+use crate::comparison_item;
+use crate::errors::Result;
+use crate::explore::ExploreResponse;
+use crate::retry::Outcome;
+#[cfg(feature = "tracing")]
+use crate::trace;
+use crate::transport::{HttpResponse, Transport};
+use crate::{
+    utils, Category, ComparisonItem, Country, Keywords, Lang, Period, Property, RateLimiter,
+    RetryPolicy, Session, TimeRange, Timezone,
+};
+#[allow(deprecated)]
 use chrono::{Date, Utc};
-use reqwest::{blocking::ClientBuilder, header, Url};
-use serde_json::Value;
+use reqwest::{header, Url};
+use serde_json::json;
 use std::string::ToString;
+use std::thread;
 use strum::EnumProperty;
 
+/// Google Trend client.
+///
+/// The HTTP client is `reqwest::blocking::Client` by default.
+/// With the `async` feature, `AsyncClient` uses the asynchronous `reqwest::Client` instead.
+///
+/// The client retries the requests rejected by Google following its [`RetryPolicy`],
+/// and paces them with its [`RateLimiter`] if one is set. Clones share the [`Session`] and the rate limiter.
 #[derive(Clone, Debug)]
-pub struct Client {
-    pub client: reqwest::blocking::Client,
-    pub cookie: Cookie,
+pub struct Client<C = reqwest::blocking::Client> {
+    pub client: C,
+    pub session: Session,
     pub country: Country,
     pub keywords: Keywords,
     pub lang: Lang,
     pub property: Property,
     pub time: String,
     pub category: Category,
-    pub response: Value,
+    pub comparison: Vec<ComparisonItem>,
+    pub timezone: Timezone,
+    pub retry: RetryPolicy,
+    pub rate_limiter: Option<RateLimiter>,
+    pub response: ExploreResponse,
 }
 
 /// Default value for client
@@ -29,44 +52,76 @@ pub struct Client {
 /// - The Country is all the countries supported by google trend
 /// - The Langage is English
 /// - The Category is 0
-/// - The response is empty (but valid json)
+/// - The Timezone is UTC
+/// - The response is empty (no widget)
+/// - The session is empty, no request is sent to retrieve it
+/// - The requests are retried with the default [`RetryPolicy`], without rate limiter
+///
+/// Use a [`ClientBuilder`](crate::ClientBuilder) to set everything up without network before retrieving the cookie.
 ///
 /// # Example
 /// ```
-/// # use rtrend::{Client, Keywords, Country};
-/// let keywords = Keywords::new(vec!["rust"]);
-/// let country = Country::FR;
-///
-/// let client = Client::new(keywords, country);
+/// # use rtrend::{Client, Keywords};
+/// let client = Client::default().with_keywords(Keywords::new(vec!["rust"]));
 ///
-/// println!("{:#?}", client);
+/// assert!(client.session.cookies().is_empty());
 /// ```
 impl Default for Client {
     fn default() -> Self {
-        Self {
-            client: reqwest::blocking::Client::default(),
-            cookie: Cookie::new(),
-            response: serde_json::from_str("{}").unwrap(),
-            keywords: Keywords::default(),
-            time: Period::OneYear.to_string(),
-            country: Country::ALL,
-            property: Property::Web,
-            lang: Lang::EN,
-            category: Category::All,
-        }
+        Self::from_parts(
+            reqwest::blocking::Client::default(),
+            Session::default(),
+            Keywords::default(),
+            Country::ALL,
+        )
     }
 }
 
+/// Asynchronous Google Trend client.
+///
+/// Available with the `async` feature. Every widget type accepts an `AsyncClient` and then exposes `async` versions of its methods.
+///
+/// # Example
+/// ```no_run
+/// # use rtrend::{AsyncClient, Keywords, Country, SearchInterest};
+/// # async fn run() -> Result<(), rtrend::Error> {
+/// let keywords = Keywords::new(vec!["rust"]);
+/// let country = Country::FR;
+///
+/// let client = AsyncClient::try_new_async(keywords, country).await?.try_build().await?;
+///
+/// let search_interest = SearchInterest::new(client).try_get().await?;
+/// println!("{:#?}", search_interest);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub type AsyncClient = Client<reqwest::Client>;
+
 impl Client {
-    const EXPLORE_ENDPOINT: &'static str = "https://trends.google.com/trends/api/explore";
-    const BAD_CHARACTER: usize = 4;
+    /// Create a [`ClientBuilder`](crate::ClientBuilder), no request is sent.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country};
+    /// # fn main() -> Result<(), rtrend::Error> {
+    /// let client = Client::builder()
+    ///     .with_keywords(Keywords::new(vec!["rust"]))
+    ///     .with_country(Country::FR)
+    ///     .connect()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> crate::ClientBuilder {
+        crate::ClientBuilder::new()
+    }
 
     /// Create a new Client.
     ///
     /// Returns a Client.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country};
     /// let keywords = Keywords::new(vec!["rust"]);
     /// let country = Country::FR;
@@ -78,23 +133,269 @@ impl Client {
     ///
     /// Will panic if the client can't be built.
     /// This can happen if the cookie can not be set or if the request time out.
+    ///
+    /// A [`ClientBuilder`](crate::ClientBuilder) sets the client up without any request.
     pub fn new(keywords: Keywords, country: Country) -> Self {
-        let mut headers = header::HeaderMap::new();
-        headers = Cookie::new().add_to_header(headers);
-        let client = ClientBuilder::new().default_headers(headers).build();
-        let client = match client {
-            Ok(client) => client,
-            Err(error) => panic!(
-                "Problem constructing the client while retrieving access token: {:?}",
+        Self::try_new(keywords, country).unwrap_or_else(|error| {
+            panic!(
+                "Problem constructing the client while retrieving access token: {}",
                 error
-            ),
-        };
+            )
+        })
+    }
+
+    /// Create a new Client without panicking.
+    ///
+    /// Returns an error if the cookie can not be set or if the request time out.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country};
+    /// # fn main() -> Result<(), rtrend::Error> {
+    /// let keywords = Keywords::new(vec!["rust"]);
+    /// let country = Country::FR;
+    ///
+    /// let client = Client::try_new(keywords, country)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_new(keywords: Keywords, country: Country) -> Result<Self> {
+        let client = reqwest::blocking::Client::new();
+        let session = Session::try_fetch(&client)?;
+
+        Ok(Self::from_parts(client, session, keywords, country))
+    }
+}
+
+impl<T: Transport> Client<T> {
+    /// Build client and send request.
+    ///
+    /// A response will be retrieve and available through the `response` field.
+    /// This field will serve for making next requests.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country};
+    /// let keywords = Keywords::new(vec!["Cat"]);
+    /// let country = Country::US;
+    ///
+    /// let client = Client::new(keywords, country).build();
+    ///
+    /// println!("{:#?}", client.response.widgets);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the request fails or if the response can't be parsed, see [`Client::try_build`].
+    pub fn build(self) -> Self {
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Build client and send request without panicking.
+    ///
+    /// Returns an error if the keywords are not between 1 and 5, if a period is not a valid [`TimeRange`], if the request fails, if Google answers with an error status or if the response can't be parsed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country};
+    /// # fn main() -> Result<(), rtrend::Error> {
+    /// let keywords = Keywords::new(vec!["Cat"]);
+    /// let country = Country::US;
+    ///
+    /// let client = Client::try_new(keywords, country)?.try_build()?;
+    ///
+    /// println!("{:#?}", client.response.widgets);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "explore", skip_all))]
+    pub fn try_build(mut self) -> Result<Self> {
+        self.keywords.check()?;
+        self.check_periods()?;
+        let resp = self.fetch(&self.explore_url())?;
+
+        self.response = utils::parse_response(&resp.body)?;
+        trace_event!(debug, widgets = self.response.widgets.len(), "explore response parsed");
+        Ok(self)
+    }
+
+    // Send a request with the session, following the retry policy and the rate limiter
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "request", skip_all, fields(url = %trace::redact(url)))
+    )]
+    pub(crate) fn fetch(&self, url: &Url) -> Result<HttpResponse> {
+        let mut attempt = 1;
+        let mut rate_limited = 0;
+
+        if self.session.is_expired() {
+            // An expired cookie may still be accepted, a failed refresh keeps it
+            self.refresh_session();
+        }
+
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire();
+            }
+            let headers = self.session.add_to_header(header::HeaderMap::new());
+
+            let retry_after = match self.retry.outcome(attempt, self.client.get(url, &headers)) {
+                Outcome::Done(result) => return result,
+                Outcome::Retry { retry_after, rate_limited: false } => retry_after,
+                Outcome::Retry { retry_after, rate_limited: true } => {
+                    rate_limited += 1;
+                    if self.retry.should_refresh_cookie(rate_limited) {
+                        // Google may reject the cookie rather than the pace, a failed refresh keeps the previous one
+                        self.refresh_session();
+                    }
+                    retry_after
+                }
+            };
+
+            let delay = retry_after.unwrap_or_else(|| self.retry.delay(attempt));
+            trace_event!(warn, attempt, rate_limited, delay = ?delay, "retrying the request");
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    fn refresh_session(&self) {
+        trace_event!(info, "refreshing the session");
+        if let Err(_error) = self.session.try_refresh(&self.client) {
+            trace_event!(warn, error = %_error, "the session can't be refreshed, the previous one is kept");
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncClient {
+    /// Create a new asynchronous Client.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the client can't be built, see [`AsyncClient::try_new_async`].
+    pub async fn new_async(keywords: Keywords, country: Country) -> Self {
+        Self::try_new_async(keywords, country)
+            .await
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Problem constructing the client while retrieving access token: {}",
+                    error
+                )
+            })
+    }
+
+    /// Create a new asynchronous Client without panicking.
+    ///
+    /// Returns an error if the cookie can not be set or if the request time out.
+    pub async fn try_new_async(keywords: Keywords, country: Country) -> Result<Self> {
+        let client = reqwest::Client::new();
+        let session = Session::try_fetch_async(&client).await?;
+
+        Ok(Self::from_parts(client, session, keywords, country))
+    }
+
+    /// Build client and send request.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the request fails or if the response can't be parsed, see [`AsyncClient::try_build`].
+    pub async fn build(self) -> Self {
+        self.try_build()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Build client and send request without panicking.
+    ///
+    /// Returns an error if the keywords are not between 1 and 5, if a period is not a valid [`TimeRange`], if the request fails, if Google answers with an error status or if the response can't be parsed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "explore", skip_all))]
+    pub async fn try_build(mut self) -> Result<Self> {
+        self.keywords.check()?;
+        self.check_periods()?;
+        let resp = self.fetch_async(&self.explore_url()).await?;
+
+        self.response = utils::parse_response(&resp.body)?;
+        trace_event!(debug, widgets = self.response.widgets.len(), "explore response parsed");
+        Ok(self)
+    }
+
+    // Asynchronous counterpart of `Client::fetch`, following the same retry policy and rate limiter
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "request", skip_all, fields(url = %trace::redact(url)))
+    )]
+    pub(crate) async fn fetch_async(&self, url: &Url) -> Result<HttpResponse> {
+        let mut attempt = 1;
+        let mut rate_limited = 0;
+
+        if self.session.is_expired() {
+            self.refresh_session_async().await;
+        }
+
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire_async().await;
+            }
 
+            let retry_after = match self.retry.outcome(attempt, self.send_async(url).await) {
+                Outcome::Done(result) => return result,
+                Outcome::Retry { retry_after, rate_limited: false } => retry_after,
+                Outcome::Retry { retry_after, rate_limited: true } => {
+                    rate_limited += 1;
+                    if self.retry.should_refresh_cookie(rate_limited) {
+                        self.refresh_session_async().await;
+                    }
+                    retry_after
+                }
+            };
+
+            let delay = retry_after.unwrap_or_else(|| self.retry.delay(attempt));
+            trace_event!(warn, attempt, rate_limited, delay = ?delay, "retrying the request");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send_async(&self, url: &Url) -> Result<HttpResponse> {
+        let headers = self.session.add_to_header(header::HeaderMap::new());
+        let resp = self.client.get(url.clone()).headers(headers).send().await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: resp.text().await?,
+        })
+    }
+
+    async fn refresh_session_async(&self) {
+        trace_event!(info, "refreshing the session");
+        if let Err(_error) = self.session.try_refresh_async(&self.client).await {
+            trace_event!(warn, error = %_error, "the session can't be refreshed, the previous one is kept");
+        }
+    }
+}
+
+impl<C> Client<C> {
+    const EXPLORE_ENDPOINT: &'static str = "https://trends.google.com/trends/api/explore";
+
+    fn from_parts(client: C, session: Session, keywords: Keywords, country: Country) -> Self {
         Self {
             client,
-            country,
+            session,
+            response: ExploreResponse::default(),
             keywords,
-            ..Client::default()
+            time: Period::OneYear.to_string(),
+            country,
+            property: Property::Web,
+            lang: Lang::EN,
+            category: Category::All,
+            comparison: Vec::new(),
+            timezone: Timezone::default(),
+            retry: RetryPolicy::default(),
+            rate_limiter: None,
         }
     }
 
@@ -103,7 +404,7 @@ impl Client {
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country};
     /// let keywords = Keywords::new(vec!["rust"]);
     /// let country = Country::FR;
@@ -116,6 +417,89 @@ impl Client {
     /// ```
     pub fn with_keywords(mut self, keywords: Keywords) -> Self {
         self.keywords = keywords;
+        self.comparison.clear();
+        self
+    }
+
+    /// Set the comparison item by item, each one with its own geo and period.
+    ///
+    /// The keywords of the items replace the ones setup during the client creation.
+    /// They are trimmed like [`Keywords`], items with an empty keyword are dropped and so are the repeated items.
+    /// The same keyword can be used several times with another geo or period, the widgets then follow the order of the items.
+    /// Geo and period not set on an item are the ones of the client.
+    ///
+    /// Returns a client instance.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, ComparisonItem, Keywords, Country, Period, SearchInterest};
+    /// let keywords = Keywords::new(vec!["rust"]);
+    /// let client = Client::new(keywords, Country::ALL).with_comparison(vec![
+    ///     ComparisonItem::new("rust").with_geo(Country::US),
+    ///     ComparisonItem::new("rust").with_geo(Country::DE),
+    ///     ComparisonItem::new("golang").with_geo(Country::US).with_period(Period::NinetyDay),
+    /// ]);
+    ///
+    /// let search_interest = SearchInterest::new(client.build()).get();
+    /// ```
+    ///
+    /// Each item gets its own widgets, even when it repeats a keyword.
+    /// ```
+    /// # use rtrend::{ClientBuilder, ComparisonItem, Country, RelatedQueries};
+    /// # use rtrend::transport::Fixtures;
+    /// let explore = r#")]}'{"widgets": [
+    ///     {"id": "RELATED_QUERIES_0", "token": "us", "request": {"restriction": {"geo": {"country": "US"},
+    ///      "complexKeywordsRestriction": {"keyword": [{"type": "BROAD", "value": "rust"}]}}}},
+    ///     {"id": "RELATED_QUERIES_1", "token": "de", "request": {"restriction": {"geo": {"country": "DE"},
+    ///      "complexKeywordsRestriction": {"keyword": [{"type": "BROAD", "value": "rust"}]}}}}
+    /// ]}"#;
+    /// let fixtures = Fixtures::new()
+    ///     .with("explore", explore)
+    ///     .with("widgetdata/relatedsearches", r#")]}',{"default": {"rankedList": [{"rankedKeyword": []}, {"rankedKeyword": []}]}}"#);
+    ///
+    /// let client = ClientBuilder::new()
+    ///     .with_comparison(vec![
+    ///         ComparisonItem::new("rust").with_geo(Country::US),
+    ///         ComparisonItem::new("rust").with_geo(Country::DE),
+    ///     ])
+    ///     .build_with(fixtures.clone())
+    ///     .unwrap()
+    ///     .try_build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(RelatedQueries::new(client).try_get().unwrap().len(), 2);
+    ///
+    /// let tokens: Vec<String> = fixtures.requests()[1..]
+    ///     .iter()
+    ///     .map(|url| url.query_pairs().find(|(key, _)| key == "token").unwrap().1.into_owned())
+    ///     .collect();
+    /// assert_eq!(tokens, vec!["us", "de"]);
+    /// ```
+    ///
+    /// Repeated items are dropped, the case of a search term doesn't matter.
+    /// ```
+    /// # use rtrend::{ClientBuilder, ComparisonItem, Country};
+    /// # use rtrend::transport::Fixtures;
+    /// let client = ClientBuilder::new()
+    ///     .build_with(Fixtures::new())
+    ///     .unwrap()
+    ///     .with_comparison(vec![
+    ///         ComparisonItem::new(" rust ").with_geo(Country::US),
+    ///         ComparisonItem::new("Rust").with_geo(Country::US),
+    ///         ComparisonItem::new("rust").with_geo(Country::DE),
+    ///         ComparisonItem::new(" "),
+    ///     ]);
+    ///
+    /// let keywords: Vec<&str> = client.keywords.iter().map(|keyword| keyword.value()).collect();
+    /// assert_eq!(keywords, vec!["rust", "rust"]);
+    /// assert_eq!(client.comparison[1].geo, Some(Country::DE));
+    /// ```
+    pub fn with_comparison(mut self, items: Vec<ComparisonItem>) -> Self {
+        let items = comparison_item::normalize(items);
+        self.keywords = Keywords {
+            keywords: items.iter().map(|item| item.keyword.clone()).collect(),
+        };
+        self.comparison = items;
         self
     }
     /// Set in which langage the response will be. The input need to be set in lowercase.
@@ -125,7 +509,7 @@ impl Client {
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country, Lang};
     /// let keywords = Keywords::new(vec!["rust"]);
     /// let country = Country::ALL;
@@ -146,7 +530,7 @@ impl Client {
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country, Category};
     /// let keywords = Keywords::new(vec!["hacking"]);
     /// let country = Country::ALL;
@@ -169,7 +553,7 @@ impl Client {
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country, Property};
     /// let keywords = Keywords::new(vec!["vlog"]);
     /// let country = Country::ALL;
@@ -186,32 +570,114 @@ impl Client {
 
     /// Set the period google trend will search on.
     ///
-    /// Period are preset set by Google Trend.
+    /// Either a [`Period`] preset set by Google Trend or any [`TimeRange`] (custom dates, hours, relative ranges).
+    /// The period is checked when the client is built.
     /// By default, the search will be made on 1 year (starting by today).
     ///
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
-    /// # use rtrend::{Client, Keywords, Country, Period};
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country, Period, TimeRange, TimeUnit};
     /// let keywords = Keywords::new(vec!["vlog"]);
     /// let country = Country::ALL;
     ///
     /// // response will concern data from this week
     /// let client = Client::new(keywords, country).with_period(Period::SevenDay);
+    ///
+    /// // or from the last 2 years
+    /// let client = client.with_period(TimeRange::Today(2, TimeUnit::Year));
     /// ```
     pub fn with_period(mut self, period: impl ToString) -> Self {
         self.time = period.to_string();
         self
     }
 
+    /// Set the timezone google trend will compute the timelines in.
+    ///
+    /// Accept a `chrono::FixedOffset` or an IANA zone from `chrono_tz`.
+    /// Days and hours of the timelines start at midnight and on the hour of this timezone,
+    /// and their points carry its offset, for an IANA zone the one in force at the end of the period
+    /// (see [`Timezone::offset_for`]).
+    /// By default, the timezone is UTC.
+    ///
+    /// Returns a client instance.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country};
+    /// let keywords = Keywords::new(vec!["croissant"]);
+    /// let country = Country::FR;
+    ///
+    /// let client = Client::new(keywords, country).with_timezone(chrono_tz::Europe::Paris);
+    /// ```
+    pub fn with_timezone(mut self, timezone: impl Into<Timezone>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Set how the requests rejected by Google are retried, see [`RetryPolicy`].
+    ///
+    /// Returns a client instance.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::{ClientBuilder, Keywords, RetryPolicy};
+    /// # use rtrend::transport::{Fixtures, HttpResponse};
+    /// # use reqwest::StatusCode;
+    /// # use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    /// # use std::time::Duration;
+    /// // The hour asked by Google is capped at the longest delay of the policy
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+    /// let too_many = HttpResponse { status: StatusCode::TOO_MANY_REQUESTS, headers, ..HttpResponse::ok("") };
+    /// let fixtures = Fixtures::new()
+    ///     .with_response("explore", too_many)
+    ///     .with("explore", r#")]}'{"widgets": []}"#);
+    ///
+    /// let policy = RetryPolicy::new(3)
+    ///     .with_base_delay(Duration::from_millis(10))
+    ///     .with_max_delay(Duration::from_millis(10));
+    /// let client = ClientBuilder::new()
+    ///     .with_keywords(Keywords::new(vec!["rust"]))
+    ///     .build_with(fixtures.clone())
+    ///     .unwrap()
+    ///     .with_retry(policy)
+    ///     .try_build();
+    ///
+    /// assert!(client.is_ok());
+    /// assert_eq!(fixtures.requests().len(), 2);
+    /// ```
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Limit the pace of the requests of the client and its clones, see [`RateLimiter`].
+    ///
+    /// Returns a client instance.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Keywords, Country, RateLimiter};
+    /// # use std::time::Duration;
+    /// let keywords = Keywords::new(vec!["rust"]);
+    ///
+    /// // At most 10 requests a minute
+    /// let client = Client::new(keywords, Country::ALL).with_rate_limit(RateLimiter::new(10, Duration::from_secs(60)));
+    /// ```
+    pub fn with_rate_limit(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     /// Set the "start date" and "end date" google trend will search on.
     /// By default, the search will be made on 1 year (starting by today).
     ///
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country};
     /// # use chrono::prelude::*;
     /// let keywords = Keywords::new(vec!["vlog"]);
@@ -223,6 +689,7 @@ impl Client {
     ///
     /// let client = Client::new(keywords, country).with_date(start_date, end_date);
     /// ```
+    #[allow(deprecated)]
     pub fn with_date(mut self, start_date: Date<Utc>, end_date: Date<Utc>) -> Self {
         fn convert(date: Date<Utc>) -> String {
             date.format("%Y-%m-%d").to_string()
@@ -240,7 +707,7 @@ impl Client {
     /// Returns a client instance.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Client, Keywords, Country, Property, Category, Lang};
     /// let keywords = Keywords::new(vec!["cat"]);
     /// let country = Country::ALL;
@@ -267,76 +734,65 @@ impl Client {
         self
     }
 
-    /// Build client and send request.
-    ///
-    /// A response will be retrieve and available through the `response` field.
-    /// This field will serve for making next requests.
-    ///
-    /// # Example
-    /// ```
-    /// # use rtrend::{Client, Keywords, Country};
-    /// let keywords = Keywords::new(vec!["Cat"]);
-    /// let country = Country::US;
-    ///
-    /// let client = Client::new(keywords, country).build();
-    ///
-    /// println!("{}", client.response);
-    /// ```
-    pub fn build(mut self) -> Self {
-        let url = Url::parse(Self::EXPLORE_ENDPOINT).unwrap();
+    // Same request on another period, including the comparison items with their own period
+    pub(crate) fn with_window(mut self, range: TimeRange) -> Self {
+        for item in self.comparison.iter_mut() {
+            item.time = None;
+        }
+        self.with_period(range)
+    }
+
+    fn check_periods(&self) -> Result<()> {
+        check_periods(&self.time, &self.comparison)
+    }
+
+    // Explore request for the keywords and filters set within the client
+    pub(crate) fn explore_url(&self) -> Url {
         let comparison_item = self.build_comparison_item();
 
-        let req = self
-            .client
-            .get(url)
-            .query(&[
+        Url::parse_with_params(
+            Self::EXPLORE_ENDPOINT,
+            &[
                 ("hl", self.lang.to_string().as_str()),
                 ("geo", self.country.to_string().as_str()),
-                ("tz", "-120"),
+                ("tz", self.timezone.param_for(&self.time).as_str()),
                 ("req", &comparison_item),
-                ("tz", "-120"),
-            ])
-			.build()
-			.unwrap();
-
-		let resp = self.client.execute(req);
-
-        let resp = match resp {
-            Ok(resp) => resp,
-            Err(error) => panic!("Can't get client response: {:?}", error),
-        };
-
-        let body = resp.text().unwrap();
-        let clean_response = utils::sanitize_response(&body, Self::BAD_CHARACTER);
-
-        self.response = serde_json::from_str(clean_response).unwrap();
-        self
+                ("tz", self.timezone.param_for(&self.time).as_str()),
+            ],
+        )
+        .unwrap()
     }
 
+    // Topics are sent by their id, Google recognizes them in the keyword field
     fn build_comparison_item(&self) -> String {
-        let mut comparison_item = String::new();
-        let keys_it = self.keywords.keywords.iter();
-
-        for key in keys_it {
-            let index_value = format!(
-                "{{
-                    'keyword':'{}',
-                    'geo':'{}',
-                    'time':'{}'
-                }},",
-                key, self.country, self.time
-            );
-
-            comparison_item.push_str(&index_value);
-        }
+        let comparison_item: Vec<_> = if self.comparison.is_empty() {
+            self.keywords
+                .iter()
+                .map(|key| ComparisonItem::new(key.clone()).to_json(&self.country, &self.time))
+                .collect()
+        } else {
+            self.comparison
+                .iter()
+                .map(|item| item.to_json(&self.country, &self.time))
+                .collect()
+        };
 
         let id = self.category.get_int("Id").unwrap_or(0);
 
-        format!(
-            "{{ 'comparisonItem': [{}], 'category':{}, 'property':'{}' }}",
-            comparison_item.as_str(),
-            id,
-            self.property
-        )
+        json!({
+            "comparisonItem": comparison_item,
+            "category": id,
+            "property": self.property.to_string(),
+        })
+        .to_string()
+    }
+}
+
+// Periods are set as strings, check them before asking Google
+pub(crate) fn check_periods(time: &str, comparison: &[ComparisonItem]) -> Result<()> {
+    time.parse::<TimeRange>()?;
+    for time in comparison.iter().filter_map(|item| item.time.as_ref()) {
+        time.parse::<TimeRange>()?;
     }
+    Ok(())
 }
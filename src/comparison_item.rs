@@ -0,0 +1,96 @@
+//! One slot of a Google Trend comparison.
+//!
+//! A comparison holds up to 5 items. Each item is a keyword with its own geo and period,
+//! so "rust in US" can be compared with "rust in DE" in a single request.
+//! Unset geo or period fall back to the ones of the client.
+
+use crate::{Country, Keyword};
+use serde_json::{json, Value};
+
+/// Create a new comparison item.
+///
+/// Returns a ComparisonItem instance.
+///
+/// # Example
+/// ```
+/// # use rtrend::{ComparisonItem, Country, Period};
+/// let item = ComparisonItem::new("rust")
+///     .with_geo(Country::DE)
+///     .with_period(Period::NinetyDay);
+///
+/// assert_eq!(item.keyword.value(), "rust");
+/// assert_eq!(item.geo, Some(Country::DE));
+/// assert_eq!(item.time.as_deref(), Some("today 3-m"));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct ComparisonItem {
+	pub keyword: Keyword,
+	pub geo: Option<Country>,
+	pub time: Option<String>,
+}
+
+impl ComparisonItem {
+    /// Create a comparison item for a keyword, using the geo and period of the client.
+    ///
+    /// Returns a ComparisonItem instance.
+    pub fn new(keyword: impl Into<Keyword>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            geo: None,
+            time: None,
+        }
+    }
+
+    /// Set the country this item will search on.
+    ///
+    /// Returns a ComparisonItem instance.
+    pub fn with_geo(mut self, geo: Country) -> Self {
+        self.geo = Some(geo);
+        self
+    }
+
+    /// Set the period this item will search on.
+    ///
+    /// Accept a [`Period`](crate::Period) or any custom period understood by Google Trend (`"2021-01-01 2021-06-30"`).
+    ///
+    /// Returns a ComparisonItem instance.
+    pub fn with_period(mut self, period: impl ToString) -> Self {
+        self.time = Some(period.to_string());
+        self
+    }
+
+    // Same keyword, geo and period
+    fn same_as(&self, other: &ComparisonItem) -> bool {
+        self.keyword.same_as(&other.keyword) && self.geo == other.geo && self.time == other.time
+    }
+
+    // Unset fields are taken from the client
+    pub(crate) fn to_json(&self, country: &Country, time: &str) -> Value {
+        json!({
+            "keyword": self.keyword.value(),
+            "geo": self.geo.as_ref().unwrap_or(country).to_string(),
+            "time": self.time.as_deref().unwrap_or(time),
+        })
+    }
+}
+
+impl<K: Into<Keyword>> From<K> for ComparisonItem {
+    fn from(keyword: K) -> Self {
+        Self::new(keyword)
+    }
+}
+
+// Trim the keywords like `Keywords` does, drop the empty ones and keep the first occurrence of duplicate items
+pub(crate) fn normalize(items: Vec<ComparisonItem>) -> Vec<ComparisonItem> {
+    let mut normalized: Vec<ComparisonItem> = Vec::with_capacity(items.len());
+    for item in items {
+        let item = ComparisonItem {
+            keyword: item.keyword.trimmed(),
+            ..item
+        };
+        if !item.keyword.value().is_empty() && !normalized.iter().any(|other| other.same_as(&item)) {
+            normalized.push(item);
+        }
+    }
+    normalized
+}
@@ -1,4 +1,7 @@
+use crate::errors::{Error, Result};
+use crate::transport::Transport;
 use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+use reqwest::Url;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Cookie {
@@ -6,26 +9,66 @@ pub struct Cookie {
 }
 
 impl Cookie {
+    /// # Panics
+    ///
+    /// Will panic if the consent cookie can't be retrieved, see [`Cookie::try_new`].
     pub fn new() -> Self {
-        Self {
-            nid: Self::get_new_cookie(),
-        }
+        Self::try_new().unwrap_or_else(|error| panic!("{}", error))
     }
 
+    pub fn try_new() -> Result<Self> {
+        Ok(Self {
+            nid: Self::try_get_new_cookie()?,
+        })
+    }
+
+    // Handshake through the transport of the client, so the proxy and the timeout apply
+    pub(crate) fn try_fetch<T: Transport>(transport: &T) -> Result<Self> {
+        let url = Url::parse(Self::COOKIE_HANDSHAKE).expect("the handshake url is valid");
+        let response = transport.get(&url, &HeaderMap::new())?;
+        Ok(Self {
+            nid: Self::parse_nid(&response.headers)?,
+        })
+    }
+
+    /// # Panics
+    ///
+    /// Will panic if the consent cookie can't be retrieved, see [`Cookie::try_get_new_cookie`].
     pub fn get_new_cookie() -> String {
-        const COOKIE_HANDSHAKE: &str =
-            "https://consent.google.com/s?continue=https://www.google.com/";
+        Self::try_get_new_cookie().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_get_new_cookie() -> Result<String> {
+        Ok(Self::try_fetch(&reqwest::blocking::Client::new())?.nid)
+    }
 
-        let response = reqwest::blocking::get(COOKIE_HANDSHAKE).unwrap();
-        let cookie = response.headers().get(SET_COOKIE).unwrap();
+    /// Asynchronous version of [`Cookie::try_new`], available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn try_new_async() -> Result<Self> {
+        Self::try_fetch_async(&reqwest::Client::new()).await
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn try_fetch_async(client: &reqwest::Client) -> Result<Self> {
+        let response = client.get(Self::COOKIE_HANDSHAKE).send().await?;
+        Ok(Self {
+            nid: Self::parse_nid(response.headers())?,
+        })
+    }
+
+    const COOKIE_HANDSHAKE: &'static str =
+        "https://consent.google.com/s?continue=https://www.google.com/";
 
-        cookie
+    fn parse_nid(headers: &HeaderMap) -> Result<String> {
+        let cookie = headers
+            .get(SET_COOKIE)
+            .ok_or_else(|| Error::Cookie("no Set-Cookie header in the handshake".to_string()))?;
+
+        let cookie = cookie
             .to_str()
-            .unwrap()
-            .to_string()
-            .split(' ')
-            .collect::<Vec<&str>>()[0]
-            .to_string()
+            .map_err(|error| Error::Cookie(error.to_string()))?;
+
+        Ok(cookie.split(' ').collect::<Vec<&str>>()[0].to_string())
     }
 
     pub fn add_to_header(&self, mut header: HeaderMap) -> HeaderMap {
@@ -33,3 +76,4 @@ impl Cookie {
         header
     }
 }
+
@@ -0,0 +1,215 @@
+//! Represent Google Trend Daily Search Trends.
+//!
+//! Searches that jumped significantly in traffic among all searches over the past 24 hours, for a given country.
+//! Each trending search comes with its approximate traffic, related queries and news articles.
+
+use chrono::NaiveDate;
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::transport::Transport;
+use crate::request_handler::Query;
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::{utils, Client};
+
+// Correpond to dailytrends request
+#[derive(Clone, Debug, Deserialize)]
+pub struct DailyTrendsEnvelope {
+	default: DailyTrendsResponse
+}
+
+/// Trending searches of one or several days, most recent day first.
+///
+/// # Example
+/// ```
+/// # use rtrend::daily_trends::DailyTrendsResponse;
+/// let body = r#"{
+///     "trendingSearchesDays": [{
+///         "date": "20240101",
+///         "formattedDate": "Monday, January 1, 2024",
+///         "trendingSearches": [{
+///             "title": {"query": "Rose Parade", "exploreLink": "/trends/explore?q=Rose+Parade&date=now+7-d&geo=US"},
+///             "formattedTraffic": "200K+",
+///             "relatedQueries": [{"query": "rose parade 2024", "exploreLink": "/trends/explore?q=rose+parade+2024"}],
+///             "image": {"newsUrl": "https://example.com/news", "source": "Example", "imageUrl": "https://example.com/image.jpg"},
+///             "articles": [{
+///                 "title": "The Rose Parade is back",
+///                 "timeAgo": "5h ago",
+///                 "source": "Example",
+///                 "url": "https://example.com/news",
+///                 "snippet": "Floats and bands..."
+///             }],
+///             "shareUrl": "https://trends.google.com/trends/trendingsearches/daily?geo=US#Rose%20Parade"
+///         }]
+///     }],
+///     "endDateForNextRequest": "20231231"
+/// }"#;
+///
+/// let response: DailyTrendsResponse = serde_json::from_str(body).unwrap();
+/// let search = &response.days[0].trending_searches[0];
+///
+/// assert_eq!(response.days[0].date.to_string(), "2024-01-01");
+/// assert_eq!(search.title.query, "Rose Parade");
+/// assert_eq!(search.articles[0].source, "Example");
+/// assert_eq!(response.end_date_for_next_request.to_string(), "2023-12-31");
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyTrendsResponse {
+	#[serde(rename = "trendingSearchesDays")]
+	pub days: Vec<TrendingSearchesDay>,
+	/// Cursor to use with [`DailyTrends::with_date`] to retrieve the previous days.
+	#[serde(with = "utils::compact_date")]
+	pub end_date_for_next_request: NaiveDate
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendingSearchesDay {
+	#[serde(with = "utils::compact_date")]
+	pub date: NaiveDate,
+	pub formatted_date: CompactString,
+	pub trending_searches: Vec<TrendingSearch>
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendingSearch {
+	pub title: TrendingQuery,
+	/// Approximate traffic, like `200K+`.
+	pub formatted_traffic: CompactString,
+	#[serde(default)]
+	pub related_queries: Vec<TrendingQuery>,
+	pub image: Option<Image>,
+	#[serde(default)]
+	pub articles: Vec<Article>,
+	#[serde(default)]
+	pub share_url: CompactString
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendingQuery {
+	pub query: CompactString,
+	#[serde(default)]
+	pub explore_link: CompactString
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Article {
+	#[serde(alias = "articleTitle")]
+	pub title: CompactString,
+	#[serde(default, alias = "time")]
+	pub time_ago: CompactString,
+	#[serde(default)]
+	pub source: CompactString,
+	pub url: CompactString,
+	#[serde(default)]
+	pub snippet: CompactString,
+	pub image: Option<Image>
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Image {
+	#[serde(default)]
+	pub news_url: CompactString,
+	#[serde(default)]
+	pub source: CompactString,
+	#[serde(alias = "imgUrl")]
+	pub image_url: CompactString
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DailyTrends<C = Client> {
+    pub client: C,
+    /// Most recent day to retrieve, today when unset.
+    pub date: Option<NaiveDate>,
+}
+
+impl<C> DailyTrends<Client<C>> {
+    /// Create a `DailyTrends` instance for the country of the client.
+    ///
+    /// The client does not need keywords nor to be built.
+    ///
+    /// Returns a `DailyTrends` instance
+    pub fn new(client: Client<C>) -> Self {
+        Self { client, date: None }
+    }
+
+    /// Retrieve the trending searches up to `date`.
+    ///
+    /// Use the `end_date_for_next_request` of a previous response to walk back through the previous days.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, DailyTrends};
+    /// let client = Client::new(Keywords::default(), Country::US);
+    /// let daily_trends = DailyTrends::new(client);
+    ///
+    /// let today = daily_trends.get();
+    /// let before = daily_trends.clone().with_date(today.end_date_for_next_request).get();
+    ///
+    /// for day in today.days.iter().chain(&before.days) {
+    ///     println!("{}: {} trending searches", day.formatted_date, day.trending_searches.len());
+    /// }
+    /// ```
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+}
+
+impl<T: Transport> DailyTrends<Client<T>> {
+    /// Retrieve the daily trending searches.
+    ///
+    /// Returns a `DailyTrendsResponse`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, DailyTrends};
+    /// let client = Client::new(Keywords::default(), Country::FR);
+    ///
+    /// let daily_trends = DailyTrends::new(client).get();
+    ///
+    /// for search in &daily_trends.days[0].trending_searches {
+    ///     println!("{} ({})", search.title.query, search.formatted_traffic);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the country of the client is `Country::ALL` or if the request fails, see [`DailyTrends::try_get`].
+    pub fn get(&self) -> DailyTrendsResponse {
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the daily trending searches without panicking.
+    ///
+    /// Returns an `Error::CountryRequired` if the country of the client is `Country::ALL`.
+    pub fn try_get(&self) -> Result<DailyTrendsResponse> {
+        Ok(self.send_request(None)?.remove(0).default)
+    }
+}
+
+#[cfg(feature = "async")]
+impl DailyTrends<AsyncClient> {
+    /// Asynchronously retrieve the daily trending searches.
+    ///
+    /// # Panics
+    /// Panic if the country of the client is `Country::ALL` or if the request fails.
+    pub async fn get(&self) -> DailyTrendsResponse {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve the daily trending searches without panicking.
+    pub async fn try_get(&self) -> Result<DailyTrendsResponse> {
+        Ok(self.send_request(None).await?.remove(0).default)
+    }
+}
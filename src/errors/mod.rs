@@ -1,25 +1,182 @@
-use std::fmt::{Display, Formatter, Result};
+//! Errors returned by the fallible (`try_`) variants of the API.
 
+use reqwest::StatusCode;
+use std::fmt::{self, Display, Formatter};
+
+/// Result type used across the crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong while talking to Google Trend.
+///
+/// Any anti JSON hijacking prefix (`)]}'`, `while(1);`, ...) is stripped before parsing a response.
+/// HTML pages, the captcha one among them, and error statuses are reported with the beginning of the body.
+///
+/// # Example
+/// ```
+/// # use rtrend::{ClientBuilder, Error, Keywords, RetryPolicy};
+/// # use rtrend::transport::{Fixtures, HttpResponse};
+/// # use reqwest::StatusCode;
+/// let connect = |fixtures: Fixtures| {
+///     ClientBuilder::new()
+///         .with_keywords(Keywords::new(vec!["rust"]))
+///         .with_retry(RetryPolicy::none())
+///         .build_with(fixtures)
+///         .unwrap()
+///         .try_build()
+/// };
+///
+/// let prefixed = Fixtures::new().with("explore", "while(1);\n{\"widgets\": []}");
+/// assert!(connect(prefixed).is_ok());
+///
+/// let captcha = HttpResponse {
+///     status: StatusCode::SERVICE_UNAVAILABLE,
+///     ..HttpResponse::ok("<html><body>Our systems have detected unusual traffic, please solve the CAPTCHA</body></html>")
+/// };
+/// let sorry = Fixtures::new().with_response("explore", captcha);
+/// assert!(matches!(connect(sorry), Err(Error::Captcha { .. })));
+///
+/// let too_many = HttpResponse { status: StatusCode::TOO_MANY_REQUESTS, ..HttpResponse::ok("Slow down") };
+/// match connect(Fixtures::new().with_response("explore", too_many)) {
+///     Err(Error::RateLimited { excerpt, .. }) => assert_eq!(excerpt, "Slow down"),
+///     other => panic!("{:?}", other.map(|client| client.response)),
+/// }
+///
+/// let gone = HttpResponse { status: StatusCode::GONE, ..HttpResponse::ok("Gone") };
+/// match connect(Fixtures::new().with_response("explore", gone)) {
+///     Err(Error::HttpStatus { status, excerpt }) => assert_eq!((status.as_u16(), excerpt.as_str()), (410, "Gone")),
+///     other => panic!("{:?}", other.map(|client| client.response)),
+/// }
+/// ```
 #[derive(Debug)]
-pub struct KeywordNotSet;
-impl Display for KeywordNotSet {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "The keyword is not set with the client !")
+#[non_exhaustive]
+pub enum Error {
+    /// The request could not be sent or its response could not be read.
+    Transport(reqwest::Error),
+    /// Google answered with an unexpected HTTP status, `excerpt` is the beginning of the body.
+    HttpStatus { status: StatusCode, excerpt: String },
+    /// Google answered with `429 Too Many Requests`, `excerpt` is the beginning of the body.
+    RateLimited { status: StatusCode, excerpt: String },
+    /// The consent handshake did not return a usable cookie.
+    Cookie(String),
+    /// A JSON document (response, cassette, session file) doesn't have the expected shape.
+    MalformedBody(serde_json::Error),
+    /// The response body is not JSON, even without its anti JSON hijacking prefix.
+    InvalidJson {
+        error: serde_json::Error,
+        excerpt: String,
+    },
+    /// Google answered with an HTML page instead of JSON.
+    HtmlPage { excerpt: String },
+    /// Google answered with its captcha page, the address is seen as sending automated traffic.
+    Captcha { excerpt: String },
+    /// The explore response does not contain the requested widget.
+    MissingWidget {
+        id: String,
+        keyword: Option<String>,
+    },
+    /// The endpoint needs a specific country, `Country::ALL` is not supported.
+    CountryRequired,
+    /// The keyword is not set with the client.
+    KeywordNotSet(String),
+    /// More than 5 keywords were given.
+    KeywordMaxCapacity,
+    /// No keyword was given.
+    KeywordMinCapacity,
+    /// The time range is not accepted by Google Trend.
+    InvalidTimeRange(String),
+    /// The anchor of a keyword universe has no interest in a batch, it can't be rescaled.
+    AnchorWithoutData(String),
+    /// A window of a stitched series has interest but shares none with the previous windows, it can't be rescaled.
+    WindowWithoutOverlap(usize),
+    /// The cassette can't be read or written, or has no response recorded for a request.
+    Cassette(String),
+    /// Every proxy of the pool has been ejected.
+    NoProxyLeft,
+    /// The resolution of the geo map is not available for the geo of the client.
+    InvalidResolution(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::Transport(error) => write!(f, "Can't get client response: {}", error),
+            Error::HttpStatus { status, excerpt } => {
+                write!(f, "Google Trend answered with status {}: {}", status, excerpt)
+            }
+            Error::RateLimited { status, excerpt } => {
+                write!(f, "Rate limited by Google Trend ({}): {}", status, excerpt)
+            }
+            Error::Cookie(reason) => write!(f, "Can't retrieve the consent cookie: {}", reason),
+            Error::MalformedBody(error) => write!(f, "Malformed response body: {}", error),
+            Error::InvalidJson { error, excerpt } => {
+                write!(f, "The response is not valid JSON ({}): {}", error, excerpt)
+            }
+            Error::HtmlPage { excerpt } => {
+                write!(f, "Google Trend answered with an HTML page instead of JSON: {}", excerpt)
+            }
+            Error::Captcha { excerpt } => write!(
+                f,
+                "Google Trend asks for a captcha, too many requests were sent from this address: {}",
+                excerpt
+            ),
+            Error::MissingWidget { id, keyword: None } => write!(
+                f,
+                "The {} widget is missing from the explore response, has the client been built ?",
+                id
+            ),
+            Error::MissingWidget {
+                id,
+                keyword: Some(keyword),
+            } => write!(
+                f,
+                "The {} widget for \"{}\" is missing from the explore response, has the client been built ?",
+                id, keyword
+            ),
+            Error::CountryRequired => {
+                write!(f, "This request needs a specific country, Country::ALL is not supported !")
+            }
+            Error::KeywordNotSet(keyword) => {
+                write!(f, "The keyword \"{}\" is not set with the client !", keyword)
+            }
+            Error::KeywordMaxCapacity => write!(f, "The maximum is 5 keywords !"),
+            Error::KeywordMinCapacity => write!(f, "At least one keyword is required !"),
+            Error::InvalidTimeRange(reason) => write!(f, "Invalid time range: {}", reason),
+            Error::AnchorWithoutData(keyword) => write!(
+                f,
+                "The anchor \"{}\" has no interest in a batch, choose another anchor !",
+                keyword
+            ),
+            Error::WindowWithoutOverlap(window) => write!(
+                f,
+                "The window {} shares no interest with the previous ones, it can't be rescaled !",
+                window
+            ),
+            Error::Cassette(reason) => write!(f, "Cassette error: {}", reason),
+            Error::NoProxyLeft => write!(f, "Every proxy of the pool has failed, none is left !"),
+            Error::InvalidResolution(reason) => write!(f, "Invalid resolution: {}", reason),
+        }
     }
 }
 
-#[derive(Debug)]
-pub struct KeywordMaxCapacity;
-impl Display for KeywordMaxCapacity {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "The maximum is 5 keywords !")
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(error) => Some(error),
+            Error::MalformedBody(error) => Some(error),
+            Error::InvalidJson { error, .. } => Some(error),
+            _ => None,
+        }
     }
 }
 
-#[derive(Debug)]
-pub struct KeywordMinCapacity;
-impl Display for KeywordMinCapacity {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "At least one keyword is required !")
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Transport(error)
     }
-}
\ No newline at end of file
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::MalformedBody(error)
+    }
+}
@@ -0,0 +1,139 @@
+//! Represent the explore response retrieved when building the client.
+//!
+//! The explore response lists the widgets of the Google Trend page.
+//! Each widget holds the request and the token needed to retrieve its data.
+
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Widgets returned by the explore endpoint.
+///
+/// # Example
+/// ```
+/// # use rtrend::explore::ExploreResponse;
+/// let body = r#"{"widgets": [
+///     {"id": "TIMESERIES", "title": "Interest over time", "token": "t0", "request": {"comparisonItem": []}},
+///     {"id": "RELATED_QUERIES_1", "title": "Related queries", "token": "t1",
+///      "request": {"restriction": {"complexKeywordsRestriction": {"keyword": [{"type": "BROAD", "value": "rust"}]}}}}
+/// ]}"#;
+///
+/// let response: ExploreResponse = serde_json::from_str(body).unwrap();
+///
+/// assert_eq!(response.widget("TIMESERIES").unwrap().token, "t0");
+/// assert_eq!(response.widget_for("RELATED_QUERIES", "rust").unwrap().token, "t1");
+/// assert!(response.widget_for("RELATED_TOPICS", "rust").is_none());
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ExploreResponse {
+	pub widgets: Vec<Widget>
+}
+
+/// A widget of the explore response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "RawWidget")]
+pub struct Widget {
+	/// Widget id, like `TIMESERIES`, `GEO_MAP`, `RELATED_TOPICS` or `RELATED_QUERIES`.
+	/// Widgets dedicated to one keyword of a comparison are suffixed by an index, like `GEO_MAP_0`.
+	pub id: CompactString,
+	pub title: CompactString,
+	pub token: CompactString,
+	pub request: Value,
+	/// Keyword the widget is about, `None` when it compares several keywords.
+	#[serde(skip_serializing)]
+	pub keyword: Option<CompactString>
+}
+
+#[derive(Deserialize)]
+struct RawWidget {
+	id: CompactString,
+	#[serde(default)]
+	title: CompactString,
+	#[serde(default)]
+	token: CompactString,
+	#[serde(default)]
+	request: Value
+}
+
+impl From<RawWidget> for Widget {
+    fn from(raw: RawWidget) -> Self {
+        let keyword = widget_keyword(&raw.request);
+        Self {
+            id: raw.id,
+            title: raw.title,
+            token: raw.token,
+            request: raw.request,
+            keyword,
+        }
+    }
+}
+
+impl ExploreResponse {
+    /// Retrieve the first widget of this kind.
+    pub fn widget(&self, id: &str) -> Option<&Widget> {
+        self.widgets.iter().find(|widget| widget.is(id))
+    }
+
+    /// Retrieve the first widget of this kind dedicated to one keyword.
+    pub fn widget_for(&self, id: &str, keyword: &str) -> Option<&Widget> {
+        self.widgets
+            .iter()
+            .find(|widget| widget.is(id) && widget.keyword.as_deref() == Some(keyword))
+    }
+
+    /// Retrieve the widget of this kind dedicated to the comparison item at `index`.
+    ///
+    /// Its id is suffixed by the index, unless the comparison has a single item.
+    /// Unlike [`ExploreResponse::widget_for`], it tells apart the items comparing the same keyword.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::explore::ExploreResponse;
+    /// // "rust" compared in the US and in Germany
+    /// let body = r#"{"widgets": [
+    ///     {"id": "RELATED_QUERIES_0", "token": "us", "request": {"restriction": {"geo": {"country": "US"},
+    ///      "complexKeywordsRestriction": {"keyword": [{"type": "BROAD", "value": "rust"}]}}}},
+    ///     {"id": "RELATED_QUERIES_1", "token": "de", "request": {"restriction": {"geo": {"country": "DE"},
+    ///      "complexKeywordsRestriction": {"keyword": [{"type": "BROAD", "value": "rust"}]}}}}
+    /// ]}"#;
+    ///
+    /// let response: ExploreResponse = serde_json::from_str(body).unwrap();
+    ///
+    /// assert_eq!(response.widget_at("RELATED_QUERIES", 0).unwrap().token, "us");
+    /// assert_eq!(response.widget_at("RELATED_QUERIES", 1).unwrap().token, "de");
+    /// assert_eq!(response.widget_for("RELATED_QUERIES", "rust").unwrap().token, "us");
+    /// assert!(response.widget_at("RELATED_QUERIES", 2).is_none());
+    /// ```
+    pub fn widget_at(&self, id: &str, index: usize) -> Option<&Widget> {
+        let suffixed = format!("{}_{}", id, index);
+        self.widgets
+            .iter()
+            .find(|widget| widget.id == suffixed.as_str())
+            .or_else(|| {
+                self.widgets
+                    .iter()
+                    .find(|widget| index == 0 && widget.id == id && widget.keyword.is_some())
+            })
+    }
+}
+
+impl Widget {
+    fn is(&self, id: &str) -> bool {
+        match self.id.strip_prefix(id) {
+            Some(suffix) => suffix.is_empty() || suffix.starts_with('_'),
+            None => false,
+        }
+    }
+}
+
+// The keyword is either in the restriction of a related searches widget,
+// or in the only comparison item of a timeseries or geo widget
+fn widget_keyword(request: &Value) -> Option<CompactString> {
+    let restriction = match &request["comparisonItem"] {
+        Value::Array(items) if items.len() == 1 => &items[0]["complexKeywordsRestriction"],
+        Value::Array(_) => return None,
+        _ => &request["restriction"]["complexKeywordsRestriction"],
+    };
+
+    restriction["keyword"][0]["value"].as_str().map(CompactString::from)
+}
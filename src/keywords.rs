@@ -1,12 +1,128 @@
 //! A list of keywords to query on Google Trend
 //! Keywords is limited to a maximum of 5 keywords.
+//!
+//! Keywords are trimmed and deduplicated, empty keywords are dropped.
+//!
+//! A keyword is either a search term, matched as typed, or a Knowledge Graph topic.
+//! Topics are normalized by Google across langages and spellings.
 
-use crate::errors::{KeywordMaxCapacity, KeywordMinCapacity};
+use crate::errors::{Error, Result as TrendResult};
+use crate::ranked_list::Topic;
+use compact_str::CompactString;
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter, Result};
+use std::iter::FromIterator;
+
+/// A keyword of the comparison.
+///
+/// # Example
+/// ```rust
+/// use rtrend::Keyword;
+/// let term = Keyword::from("rust");
+/// let topic = Keyword::topic("/m/0dgw9r", "Rust");
+///
+/// assert_eq!(term.value(), "rust");
+/// assert_eq!(topic.value(), "/m/0dgw9r");
+/// assert_eq!(topic.to_string(), "Rust");
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Keyword {
+    /// A search term, like `rust`.
+    Term(CompactString),
+    /// A Knowledge Graph topic, like `/m/0dgw9r`, with its display name.
+    Topic {
+        mid: CompactString,
+        name: CompactString,
+    },
+}
+
+impl Keyword {
+    /// Create a topic keyword from its Knowledge Graph id and its display name.
+    pub fn topic(mid: &str, name: &str) -> Self {
+        Keyword::Topic {
+            mid: mid.into(),
+            name: name.into(),
+        }
+    }
+
+    /// Value sent to Google Trend, the term or the topic id.
+    pub fn value(&self) -> &str {
+        match self {
+            Keyword::Term(term) => term,
+            Keyword::Topic { mid, .. } => mid,
+        }
+    }
+
+    /// Display name, the term or the topic name.
+    pub fn name(&self) -> &str {
+        match self {
+            Keyword::Term(term) => term,
+            Keyword::Topic { mid, name } if name.is_empty() => mid,
+            Keyword::Topic { name, .. } => name,
+        }
+    }
+
+    // A keyword can be designated by its value or by its display name
+    fn matches(&self, keyword: &str) -> bool {
+        self.value() == keyword || self.name() == keyword
+    }
+
+    pub(crate) fn trimmed(self) -> Self {
+        match self {
+            Keyword::Term(term) => Keyword::Term(term.trim().into()),
+            Keyword::Topic { mid, name } => Keyword::Topic {
+                mid: mid.trim().into(),
+                name: name.trim().into(),
+            },
+        }
+    }
+
+    // Google Trend does not differentiate case for search terms, but topic ids are case sensitive
+    pub(crate) fn same_as(&self, other: &Keyword) -> bool {
+        match (self, other) {
+            (Keyword::Term(a), Keyword::Term(b)) => a.to_lowercase() == b.to_lowercase(),
+            _ => self.value() == other.value(),
+        }
+    }
+}
+
+impl From<&str> for Keyword {
+    fn from(term: &str) -> Self {
+        Keyword::Term(term.into())
+    }
+}
+
+impl From<String> for Keyword {
+    fn from(term: String) -> Self {
+        Keyword::Term(term.into())
+    }
+}
+
+impl From<CompactString> for Keyword {
+    fn from(term: CompactString) -> Self {
+        Keyword::Term(term)
+    }
+}
+
+/// Use a topic retrieved from [`Suggestions`](crate::Suggestions) or from related topics as a keyword.
+impl From<Topic> for Keyword {
+    fn from(topic: Topic) -> Self {
+        Keyword::Topic {
+            mid: topic.mid,
+            name: topic.title,
+        }
+    }
+}
+
+impl Display for Keyword {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.name())
+    }
+}
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
 pub struct Keywords {
-    pub keywords: Vec<&'static str>,
+    pub keywords: Vec<Keyword>,
 }
 
 impl Keywords {
@@ -36,32 +152,145 @@ impl Keywords {
     /// let keywords = Keywords::new(vec![]);
     /// ```
     pub fn new(keywords: Vec<&'static str>) -> Self {
+        Self::try_new(keywords).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Create a new set of keywords without panicking.
+    ///
+    /// Returns an `Error::KeywordMinCapacity` or `Error::KeywordMaxCapacity` if the vector is empty or holds more than 5 keywords.
+    ///
+    /// # Example
+    ///```rust
+    /// use rtrend::{Error, Keywords};
+    /// let seven_dwarf = vec!["Bashful","Doc", "Dopey","Grumpy","Happy", "Sleepy", "Sneezy"];
+    ///
+    /// assert!(matches!(Keywords::try_new(seven_dwarf), Err(Error::KeywordMaxCapacity)));
+    /// ```
+    pub fn try_new(keywords: Vec<&'static str>) -> TrendResult<Self> {
+        Self::try_from(keywords.into_iter().map(Keyword::from).collect::<Vec<_>>())
+    }
+
+    /// Iterate over the keywords, in the order of the comparison.
+    pub fn iter(&self) -> std::slice::Iter<'_, Keyword> {
+        self.keywords.iter()
+    }
+
+    /// Retrieve a keyword by its value (term or topic id) or by its display name.
+    pub fn find(&self, keyword: &str) -> Option<&Keyword> {
+        self.keywords.iter().find(|key| key.matches(keyword))
+    }
+
+    // Ensure the set can be sent to Google Trend
+    pub(crate) fn check(&self) -> TrendResult<()> {
+        check_capacity(self.keywords.len())
+    }
+
+    // Retrieve a keyword which has to be part of the set
+    pub(crate) fn resolve(&self, keyword: &str) -> TrendResult<&Keyword> {
+        self.find(keyword)
+            .ok_or_else(|| Error::KeywordNotSet(keyword.to_string()))
+    }
+}
+
+/// Create a set of keywords mixing search terms and topics.
+///
+/// # Example
+///```rust
+/// use std::convert::TryFrom;
+/// use rtrend::{Keyword, Keywords};
+///
+/// let keywords = Keywords::try_from(vec![
+///     Keyword::from("rust"),
+///     Keyword::topic("/m/0dgw9r", "Rust"),
+/// ]).unwrap();
+///
+/// assert_eq!(keywords.find("Rust").unwrap().value(), "/m/0dgw9r");
+/// ```
+impl TryFrom<Vec<Keyword>> for Keywords {
+    type Error = Error;
+
+    fn try_from(keywords: Vec<Keyword>) -> TrendResult<Self> {
+        Ok(Self {
+            keywords: check_keywords(normalize(keywords))?,
+        })
+    }
+}
+
+/// Create a set of keywords from owned strings, like keywords read from a database or from the command line.
+///
+/// Returns an `Error::KeywordMinCapacity` or `Error::KeywordMaxCapacity` if there is no keyword or more than 5 keywords once normalized.
+///
+/// # Example
+///```rust
+/// use std::convert::TryFrom;
+/// use rtrend::Keywords;
+///
+/// let input = vec![" rust ".to_string(), "Rust".to_string(), "go".to_string(), "".to_string()];
+/// let keywords = Keywords::try_from(input).unwrap();
+///
+/// assert_eq!(keywords.to_string(), Keywords::new(vec!["rust", "go"]).to_string());
+/// ```
+impl TryFrom<Vec<String>> for Keywords {
+    type Error = Error;
+
+    fn try_from(keywords: Vec<String>) -> TrendResult<Self> {
+        Self::try_from(keywords.into_iter().map(Keyword::from).collect::<Vec<_>>())
+    }
+}
+
+/// Collect keywords, they are normalized but the capacity is only checked when building the client.
+///
+/// # Example
+///```rust
+/// use rtrend::Keywords;
+///
+/// let keywords: Keywords = "rust, go, rust".split(',').collect();
+///
+/// assert_eq!(keywords.iter().count(), 2);
+/// ```
+impl<K: Into<Keyword>> FromIterator<K> for Keywords {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
         Self {
-            keywords: check_keywords(keywords),
+            keywords: normalize(iter.into_iter().map(Into::into).collect()),
         }
     }
 }
 
 impl From<&'static str> for Keywords {
     fn from(item: &'static str) -> Self {
-        Self {
-            keywords: check_keywords(item.split(',').collect()),
+        Self::new(item.split(',').collect())
+    }
+}
+
+// Trim keywords, drop empty ones and keep the first occurrence of duplicates
+fn normalize(keys: Vec<Keyword>) -> Vec<Keyword> {
+    let mut normalized: Vec<Keyword> = Vec::with_capacity(keys.len());
+    for key in keys.into_iter().map(Keyword::trimmed) {
+        if !key.value().is_empty() && !normalized.iter().any(|other| other.same_as(&key)) {
+            normalized.push(key);
         }
     }
+    normalized
+}
+
+fn check_keywords(keys: Vec<Keyword>) -> TrendResult<Vec<Keyword>> {
+    check_capacity(keys.len())?;
+    Ok(keys)
 }
 
-fn check_keywords(keys: Vec<&'static str>) -> Vec<&'static str> {
-    if keys.is_empty() {
-        Err(KeywordMinCapacity).unwrap()
+fn check_capacity(len: usize) -> TrendResult<()> {
+    if len == 0 {
+        return Err(Error::KeywordMinCapacity);
     }
-    if keys.len() > 5 {
-        Err(KeywordMaxCapacity).unwrap()
+    if len > 5 {
+        return Err(Error::KeywordMaxCapacity);
     }
-    keys
+    Ok(())
 }
 
 impl Display for Keywords {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{:#?}", self.keywords)
+        let names: Vec<&str> = self.keywords.iter().map(Keyword::name).collect();
+        write!(f, "{:#?}", names)
     }
 }
@@ -18,7 +18,7 @@
 //! ```
 //! 
 //! Then build a client and send the reqwest you want : 
-//! ```rust
+//! ```rust,no_run
 //! use rtrend::{Keywords, Country, Client, RegionInterest};
 //! 
 //! let country = Country::US;
@@ -27,7 +27,7 @@
 //! 
 //! // Then select the data you want. The interest of your keywords filtered by region for example:
 //! let region_interest = RegionInterest::new(client).get();
-//! println!("{}", region_interest);
+//! println!("{:#?}", region_interest);
 //! 
 //! // Result :
 //! //{
@@ -70,6 +70,23 @@
 //! 
 //! ```
 //! 
+//! ### Async
+//!
+//! Enable the `async` feature to use `AsyncClient`, every widget type then exposes `async` versions of its methods:
+//! ```toml
+//! [dependencies]
+//! rtrend = { version = "0.1.3", features = ["async"] }
+//! ```
+//!
+//! ### Tracing
+//!
+//! Nothing is printed by the crate. Enable the `tracing` feature to get `tracing` spans and events for the explore request,
+//! each widget call, the retries and the parse failures, widget tokens and cookies are redacted:
+//! ```toml
+//! [dependencies]
+//! rtrend = { version = "0.1.3", features = ["tracing"] }
+//! ```
+//!
 //! ### More example
 //! - [Simple](./examples/simple.rs)
 //! - [Region Interest](./examples/region_interest.rs)
@@ -78,15 +95,19 @@
 //! - [Related Topics](./examples/related_topics.rs)
 //! - [Use filters](./examples/filter.rs)
 //! - [Get response for specific keyword](./examples/select_keyword.rs)
+//! - [Daily trending searches](./examples/daily_trends.rs)
+//! - [Realtime trending stories](./examples/realtime_trends.rs)
+//! - [Topic suggestions](./examples/suggestions.rs)
+//! - [Async search interest](./examples/async_search_interest.rs)
 //! 
 //! ### Roadmap
 //! 
 //! - [x] Write documentation & Doc Test
 //! - [x] Release on crates.io
 //! - [x] Add examples
-//! - [ ] Add "TOP" and "RISING" filter
+//! - [x] Add "TOP" and "RISING" filter
 //! - [ ] Write more tests
-//! - [ ] Make async feature (currently using `Reqwest::blocking`)
+//! - [x] Make async feature (`async`, `Reqwest::blocking` stays the default)
 //! 
 //! 
 //! ### License
@@ -107,34 +128,67 @@
 //! dual licensed as above, without any additional terms or conditions.
 
 
+#[macro_use]
+mod trace;
+
 pub mod client;
+pub mod builder;
+pub mod explore;
 
 pub mod region_interest;
 pub mod search_interest;
+pub mod stitch;
+pub mod universe;
 pub mod related_queries;
 pub mod related_topics;
+pub mod daily_trends;
+pub mod realtime_trends;
+pub mod suggestions;
+pub mod ranked_list;
 
 pub mod category;
 pub mod country;
 pub mod keywords;
+pub mod comparison_item;
 pub mod lang;
 pub mod property;
+pub mod resolution;
 pub mod period;
+pub mod timezone;
+pub mod transport;
+pub mod retry;
+pub mod proxy;
+#[cfg(feature = "cassette")]
+pub mod cassette;
 
 mod request_handler;
 mod cookie;
+mod session;
 mod errors;
 mod utils;
 
 pub use client::Client;
+pub use builder::ClientBuilder;
+#[cfg(feature = "async")]
+pub use client::AsyncClient;
 pub use region_interest::RegionInterest;
 pub use search_interest::SearchInterest;
 pub use related_queries::RelatedQueries;
 pub use related_topics::RelatedTopics;
+pub use daily_trends::DailyTrends;
+pub use realtime_trends::RealtimeTrends;
+pub use suggestions::Suggestions;
+pub use universe::KeywordUniverse;
 pub use category::Category;
 pub use country::Country;
-pub use keywords::Keywords;
+pub use keywords::{Keyword, Keywords};
+pub use comparison_item::ComparisonItem;
 pub use lang::Lang;
 pub use property::Property;
+pub use resolution::Resolution;
 pub use cookie::Cookie;
-pub use period::Period;
\ No newline at end of file
+pub use session::{Session, SessionCookie};
+pub use retry::{RateLimiter, RetryPolicy};
+pub use period::{Period, TimeRange, TimeUnit};
+pub use timezone::Timezone;
+pub use errors::{Error, Result};
\ No newline at end of file
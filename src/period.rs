@@ -2,7 +2,11 @@
 //! 
 //! All period available [here](https://github.com/shadawck/rust-trend/wiki/period)
 
-use strum_macros::{EnumString, ToString};
+use crate::errors::{Error, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Timelike, Utc};
+use std::fmt;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
 
 /// Create a predefined Period.
 ///
@@ -13,7 +17,7 @@ use strum_macros::{EnumString, ToString};
 /// # use rtrend::Period;
 /// let lang = Period::OneDay;
 /// ```
-#[derive(PartialEq, Debug, EnumString, Clone, ToString)]
+#[derive(PartialEq, Debug, EnumString, Clone, Display)]
 pub enum Period {
     #[strum(serialize = "now 1-H")]
     OneHour,
@@ -38,3 +42,214 @@ pub enum Period {
     #[strum(serialize = "all")]
     Since2004,
 }
+
+/// Unit of a relative [`TimeRange`].
+#[derive(PartialEq, Eq, Debug, EnumString, Clone, Copy, Display)]
+pub enum TimeUnit {
+    #[strum(serialize = "H")]
+    Hour,
+    #[strum(serialize = "d")]
+    Day,
+    #[strum(serialize = "m")]
+    Month,
+    #[strum(serialize = "y")]
+    Year,
+}
+
+/// Any time window Google Trend can search on.
+///
+/// - `Preset` : one of the predefined [`Period`]
+/// - `Dates` : custom range with a day precision (`2021-01-01 2021-06-30`)
+/// - `Hours` : custom range with an hour precision (`2024-01-01T00 2024-01-07T12`), 7 days at most
+/// - `Now` : the last hours or days (`now 3-d`), 7 days at most
+/// - `Today` : the last months or years (`today 2-y`)
+///
+/// A time range can be given to [`Client::with_period`](crate::Client::with_period).
+/// It is validated when the client is built, [`TimeRange::check`] validates it beforehand.
+///
+/// # Example
+/// ```
+/// # use rtrend::{Period, TimeRange, TimeUnit};
+/// # use chrono::NaiveDate;
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2024, 1, 7).unwrap().and_hms_opt(12, 0, 0).unwrap();
+///
+/// let hours = TimeRange::Hours(start, end);
+/// assert_eq!(hours.to_string(), "2024-01-01T00 2024-01-07T12");
+///
+/// let two_years: TimeRange = "today 2-y".parse().unwrap();
+/// assert_eq!(two_years, TimeRange::Today(2, TimeUnit::Year));
+///
+/// assert_eq!(TimeRange::from(Period::SevenDay).to_string(), "now 7-d");
+/// assert!(TimeRange::Now(8, TimeUnit::Day).check().is_err());
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+pub enum TimeRange {
+    Preset(Period),
+    Dates(NaiveDate, NaiveDate),
+    Hours(NaiveDateTime, NaiveDateTime),
+    Now(u32, TimeUnit),
+    Today(u32, TimeUnit),
+}
+
+impl TimeRange {
+    // Google Trend has no data before 2004
+    const FIRST_DAY: (i32, u32, u32) = (2004, 1, 1);
+    // Hourly data is only available on a week
+    const MAX_HOURS: i64 = 7 * 24;
+
+    /// Check the time range against what Google Trend accepts.
+    ///
+    /// Returns an error if the range is empty, starts before 2004, asks for hourly data on more than 7 days
+    /// or uses a unit Google doesn't accept with `now` (hours and days) or `today` (months and years).
+    pub fn check(&self) -> Result<()> {
+        let first_day =
+            NaiveDate::from_ymd_opt(Self::FIRST_DAY.0, Self::FIRST_DAY.1, Self::FIRST_DAY.2)
+                .expect("valid date");
+
+        match self {
+            TimeRange::Preset(_) => Ok(()),
+            TimeRange::Dates(start, end) => {
+                if start > end {
+                    return invalid(self, "the start date is after the end date");
+                }
+                if *start < first_day {
+                    return invalid(self, "Google Trend has no data before 2004");
+                }
+                Ok(())
+            }
+            TimeRange::Hours(start, end) => {
+                if start >= end {
+                    return invalid(self, "the start hour is not before the end hour");
+                }
+                if start.date() < first_day {
+                    return invalid(self, "Google Trend has no data before 2004");
+                }
+                if start.minute() != 0
+                    || start.second() != 0
+                    || end.minute() != 0
+                    || end.second() != 0
+                {
+                    return invalid(self, "hourly ranges must start and end on the hour");
+                }
+                if (*end - *start).num_hours() > Self::MAX_HOURS {
+                    return invalid(self, "hourly ranges can't be longer than 7 days");
+                }
+                Ok(())
+            }
+            TimeRange::Now(count, unit) => {
+                let hours = match unit {
+                    TimeUnit::Hour => i64::from(*count),
+                    TimeUnit::Day => i64::from(*count) * 24,
+                    _ => return invalid(self, "`now` only accepts hours and days"),
+                };
+                if *count == 0 {
+                    return invalid(self, "the range is empty");
+                }
+                if hours > Self::MAX_HOURS {
+                    return invalid(self, "`now` can't go further than 7 days");
+                }
+                Ok(())
+            }
+            TimeRange::Today(count, unit) => {
+                if !matches!(unit, TimeUnit::Month | TimeUnit::Year) {
+                    return invalid(self, "`today` only accepts months and years");
+                }
+                if *count == 0 {
+                    return invalid(self, "the range is empty");
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl TimeRange {
+    // End of a custom range, `None` for the ranges ending now
+    pub(crate) fn end(&self) -> Option<DateTime<Utc>> {
+        match self {
+            TimeRange::Dates(_, end) => end.and_hms_opt(0, 0, 0).map(|end| end.and_utc()),
+            TimeRange::Hours(_, end) => Some(end.and_utc()),
+            _ => None,
+        }
+    }
+}
+
+fn invalid(range: &TimeRange, reason: &str) -> Result<()> {
+    Err(Error::InvalidTimeRange(format!("{} ({})", range, reason)))
+}
+
+impl From<Period> for TimeRange {
+    fn from(period: Period) -> Self {
+        TimeRange::Preset(period)
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeRange::Preset(period) => write!(f, "{}", period),
+            TimeRange::Dates(start, end) => {
+                write!(f, "{} {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+            }
+            TimeRange::Hours(start, end) => write!(
+                f,
+                "{} {}",
+                start.format("%Y-%m-%dT%H"),
+                end.format("%Y-%m-%dT%H")
+            ),
+            TimeRange::Now(count, unit) => write!(f, "now {}-{}", count, unit),
+            TimeRange::Today(count, unit) => write!(f, "today {}-{}", count, unit),
+        }
+    }
+}
+
+/// Parse the format used by Google Trend, the time range is checked.
+impl FromStr for TimeRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let malformed = || Error::InvalidTimeRange(format!("{} (unknown format)", s));
+
+        let range = if let Ok(period) = Period::from_str(s) {
+            TimeRange::Preset(period)
+        } else if let Some((kind, relative)) = s
+            .split_once(' ')
+            .filter(|(kind, _)| *kind == "now" || *kind == "today")
+        {
+            let (count, unit) = relative.split_once('-').ok_or_else(malformed)?;
+            let count = count.parse().map_err(|_| malformed())?;
+            let unit = TimeUnit::from_str(unit).map_err(|_| malformed())?;
+
+            if kind == "now" {
+                TimeRange::Now(count, unit)
+            } else {
+                TimeRange::Today(count, unit)
+            }
+        } else {
+            let (start, end) = s.split_once(' ').ok_or_else(malformed)?;
+
+            if start.contains('T') {
+                let start = parse_hour(start).ok_or_else(malformed)?;
+                let end = parse_hour(end).ok_or_else(malformed)?;
+                TimeRange::Hours(start, end)
+            } else {
+                let start =
+                    NaiveDate::parse_from_str(start, "%Y-%m-%d").map_err(|_| malformed())?;
+                let end = NaiveDate::parse_from_str(end, "%Y-%m-%d").map_err(|_| malformed())?;
+                TimeRange::Dates(start, end)
+            }
+        };
+
+        range.check()?;
+        Ok(range)
+    }
+}
+
+// chrono can't parse a datetime without minutes (`2024-01-01T00`)
+fn parse_hour(s: &str) -> Option<NaiveDateTime> {
+    let (date, hour) = s.split_once('T')?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    date.and_hms_opt(hour.parse().ok()?, 0, 0)
+}
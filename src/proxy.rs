@@ -0,0 +1,243 @@
+//! Spread the requests of a client over several proxies.
+//!
+//! Google Trend bans the addresses sending too many requests.
+//! A [`ProxyPool`] is a [`Transport`] sending each request through one of its proxies,
+//! each proxy with its own session, and ejecting the proxies which keep failing.
+
+use crate::errors::{Error, Result};
+use crate::transport::{HttpResponse, Transport};
+use crate::session::{self, Session};
+use reqwest::header::{HeaderMap, COOKIE};
+use reqwest::{StatusCode, Url};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// When a [`ProxyPool`] moves on to its next proxy.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Rotation {
+    /// Every request goes through the next proxy.
+    RoundRobin,
+    /// The same proxy is used until it is rate limited or fails.
+    OnRateLimit,
+}
+
+/// Transport sending the requests through a pool of HTTP or SOCKS proxies.
+///
+/// The session of the client is replaced by the one of the proxy, retrieved through the proxy on its first use
+/// and retrieved again once the proxy has been rate limited or its session has expired.
+/// A proxy failing `max_failures` times in a row (connection refused, timeout, ...) is ejected from the pool,
+/// once every proxy is ejected the requests fail with `Error::NoProxyLeft`.
+/// Clones share the pool.
+///
+/// # Example
+/// ```
+/// # use rtrend::proxy::{ProxyPool, Rotation};
+/// # use rtrend::transport::Fixtures;
+/// # use rtrend::{ClientBuilder, Error, Keywords};
+/// // Fixtures answering nothing stand for broken proxies, their session can't be retrieved
+/// let pool = ProxyPool::from_transports(vec![("http://10.0.0.1:3128", Fixtures::new()), ("socks5://10.0.0.2:1080", Fixtures::new())])
+///     .with_rotation(Rotation::RoundRobin)
+///     .with_max_failures(1);
+///
+/// let connect = || {
+///     ClientBuilder::new()
+///         .with_keywords(Keywords::new(vec!["rust"]))
+///         .build_with(pool.clone())
+///         .unwrap()
+///         .try_build()
+/// };
+///
+/// assert!(matches!(connect(), Err(Error::Cookie(_))));
+/// assert!(matches!(connect(), Err(Error::Cookie(_))));
+/// assert!(matches!(connect(), Err(Error::NoProxyLeft)));
+/// assert!(pool.proxies().is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProxyPool<T = reqwest::blocking::Client> {
+    state: Arc<Mutex<PoolState<T>>>,
+    rotation: Rotation,
+    max_failures: u32,
+}
+
+#[derive(Debug)]
+struct PoolState<T> {
+    proxies: Vec<Proxy<T>>,
+    next: usize,
+}
+
+#[derive(Debug)]
+struct Proxy<T> {
+    url: String,
+    transport: T,
+    session: Option<Session>,
+    failures: u32,
+}
+
+impl ProxyPool {
+    /// Create a pool of proxies (`http://`, `https://` or `socks5://` urls), with a timeout for every request.
+    ///
+    /// By default, the proxies are used in turn and ejected after 3 failures in a row.
+    ///
+    /// Returns an error if a proxy url is invalid.
+    pub fn new<S: AsRef<str>>(proxies: &[S], timeout: Option<Duration>) -> Result<Self> {
+        let proxies = proxies
+            .iter()
+            .map(|url| {
+                let mut builder = reqwest::blocking::ClientBuilder::new()
+                    .proxy(reqwest::Proxy::all(url.as_ref())?);
+                if let Some(timeout) = timeout {
+                    builder = builder.timeout(timeout);
+                }
+                Ok((url.as_ref().to_string(), builder.build()?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from_transports(proxies))
+    }
+}
+
+impl<T: Transport> ProxyPool<T> {
+    /// Create a pool sending the requests of each proxy through its own transport, the proxy being named by its url.
+    ///
+    /// By default, the proxies are used in turn and ejected after 3 failures in a row.
+    ///
+    /// Returns a `ProxyPool` instance.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::proxy::ProxyPool;
+    /// # use rtrend::transport::{Fixtures, HttpResponse, Transport};
+    /// # use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+    /// # use reqwest::Url;
+    /// let working = || {
+    ///     let mut headers = HeaderMap::new();
+    ///     headers.insert(SET_COOKIE, HeaderValue::from_static("NID=511=fresh; Max-Age=15552000; Domain=.google.com"));
+    ///     Fixtures::new()
+    ///         .with_response("/s", HttpResponse { headers, ..HttpResponse::ok("") })
+    ///         .with("explore", r#")]}'{"widgets": []}"#)
+    /// };
+    /// let (first, second) = (working(), working());
+    ///
+    /// let pool = ProxyPool::from_transports(vec![("down", Fixtures::new()), ("first", first.clone()), ("second", second)])
+    ///     .with_max_failures(2);
+    /// let explore = Url::parse("https://trends.google.com/trends/api/explore").unwrap();
+    /// let get = || pool.get(&explore, &HeaderMap::new());
+    ///
+    /// // `down` fails twice, the second time once the others have been used, and is ejected
+    /// assert!(get().is_err());
+    /// assert!(get().is_ok() && get().is_ok());
+    /// assert!(get().is_err());
+    /// assert_eq!(pool.proxies(), vec!["first", "second"]);
+    ///
+    /// // The proxy following the ejected one is not skipped
+    /// get().unwrap();
+    /// assert_eq!(first.requests().len(), 3);
+    /// ```
+    pub fn from_transports<S: Into<String>>(proxies: Vec<(S, T)>) -> Self {
+        let proxies = proxies
+            .into_iter()
+            .map(|(url, transport)| Proxy {
+                url: url.into(),
+                transport,
+                session: None,
+                failures: 0,
+            })
+            .collect();
+
+        Self {
+            state: Arc::new(Mutex::new(PoolState { proxies, next: 0 })),
+            rotation: Rotation::RoundRobin,
+            max_failures: 3,
+        }
+    }
+
+    /// Set when the pool moves on to its next proxy.
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Eject a proxy after `max_failures` failures in a row, at least one.
+    pub fn with_max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    /// Urls of the proxies still in the pool.
+    pub fn proxies(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state.proxies.iter().map(|proxy| proxy.url.clone()).collect()
+    }
+
+    // Proxy to use for the next request, the lock is not held while sending it
+    fn pick(&self) -> Result<(String, T, Option<Session>)> {
+        let state = self.state.lock().unwrap();
+        if state.proxies.is_empty() {
+            return Err(Error::NoProxyLeft);
+        }
+
+        let index = state.next % state.proxies.len();
+        let proxy = &state.proxies[index];
+        Ok((proxy.url.clone(), proxy.transport.clone(), proxy.session.clone()))
+    }
+
+    fn report(&self, url: &str, status: Option<StatusCode>, session: Option<Session>) {
+        let mut state = self.state.lock().unwrap();
+        let index = match state.proxies.iter().position(|proxy| proxy.url == url) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let rate_limited = status == Some(StatusCode::TOO_MANY_REQUESTS);
+        let proxy = &mut state.proxies[index];
+        match status {
+            Some(_) => proxy.failures = 0,
+            None => proxy.failures += 1,
+        }
+        // A rate limited proxy gets a new session on its next request
+        proxy.session = if rate_limited { None } else { session };
+
+        if proxy.failures >= self.max_failures {
+            // The proxy following the ejected one takes its index, the next one keeps its place
+            let next = state.next % state.proxies.len();
+            state.proxies.remove(index);
+            state.next = if index < next { next - 1 } else { next };
+            return;
+        }
+        if self.rotation == Rotation::RoundRobin || rate_limited || status.is_none() {
+            state.next = index + 1;
+        }
+    }
+}
+
+impl<T: Transport> Transport for ProxyPool<T> {
+    fn get(&self, url: &Url, headers: &HeaderMap) -> Result<HttpResponse> {
+        let (proxy, transport, session) = self.pick()?;
+
+        // The handshake is sent as is, every other request with the session of the proxy, retrieved on its first use
+        let handshake = session::is_handshake(url);
+        let session = match session {
+            Some(session) if handshake || !session.is_expired() => Some(session),
+            _ if handshake => None,
+            _ => match Session::try_fetch(&transport) {
+                Ok(session) => Some(session),
+                Err(error) => {
+                    self.report(&proxy, None, None);
+                    return Err(error);
+                }
+            },
+        };
+
+        let mut headers = headers.clone();
+        if !handshake {
+            headers.remove(COOKIE);
+            if let Some(session) = &session {
+                headers = session.add_to_header(headers);
+            }
+        }
+
+        let result = transport.get(url, &headers);
+        self.report(&proxy, result.as_ref().ok().map(|response| response.status), session);
+        result
+    }
+}
@@ -0,0 +1,108 @@
+//! Ranked lists returned by the Related Queries and Related Topics widgets.
+//!
+//! Each list comes in two flavours:
+//! - Top - The most popular entries, scored on a relative scale from 0 to 100.
+//! - Rising - The entries with the biggest increase in search frequency, scored as a percentage of increase.
+//!   Entries marked "Breakout" had a tremendous increase (more than 5000%).
+
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+
+// Correpond to relatedsearches request
+#[derive(Clone, Debug, Deserialize)]
+pub struct RelatedSearchesResponse {
+	default: RankedLists
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RankedLists {
+	ranked_list: Vec<RankedKeywords>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RankedKeywords {
+	ranked_keyword: Vec<RankedKeyword>
+}
+
+/// Top and Rising entries related to one keyword.
+///
+/// # Example
+/// ```
+/// # use rtrend::ranked_list::{RankedItem, RankedList, RelatedSearchesResponse};
+/// let body = r#"{"default": {"rankedList": [
+///     {"rankedKeyword": [
+///         {"query": "rust lang", "value": 100, "formattedValue": "100", "link": "/trends/explore?q=rust+lang"}
+///     ]},
+///     {"rankedKeyword": [
+///         {"topic": {"mid": "/m/0dgw9r", "title": "Rust", "type": "Programming language"},
+///          "value": 185750, "formattedValue": "Breakout", "link": "/trends/explore?q=/m/0dgw9r"},
+///         {"query": "rust book", "value": 250, "formattedValue": "+250%", "link": "/trends/explore?q=rust+book"}
+///     ]}
+/// ]}}"#;
+///
+/// let response: RelatedSearchesResponse = serde_json::from_str(body).unwrap();
+/// let list = RankedList::from(response);
+///
+/// assert_eq!(list.top[0].item, RankedItem::Query("rust lang".into()));
+/// assert!(!list.top[0].is_breakout);
+/// assert!(list.rising[0].is_breakout);
+/// assert!(!list.rising[1].is_breakout);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct RankedList {
+	pub top: Vec<RankedKeyword>,
+	pub rising: Vec<RankedKeyword>
+}
+
+/// An entry of a ranked list.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedKeyword {
+	#[serde(flatten)]
+	pub item: RankedItem,
+	/// Relative score for Top entries, percentage of increase for Rising entries.
+	pub value: u32,
+	pub formatted_value: CompactString,
+	/// The entry is a Rising entry marked "Breakout".
+	#[serde(default)]
+	pub is_breakout: bool,
+	/// Google Trend explore link for this entry.
+	#[serde(default)]
+	pub link: CompactString
+}
+
+/// A related search query or a related topic.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RankedItem {
+	Query(CompactString),
+	Topic(Topic)
+}
+
+/// A Knowledge Graph topic.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Topic {
+	/// Knowledge Graph id, like `/m/0dgw9r`.
+	pub mid: CompactString,
+	pub title: CompactString,
+	/// Kind of topic, like `Programming language`.
+	#[serde(rename = "type")]
+	pub kind: CompactString
+}
+
+impl From<RelatedSearchesResponse> for RankedList {
+    fn from(response: RelatedSearchesResponse) -> Self {
+        let mut lists = response.default.ranked_list.into_iter();
+        let top = lists.next().map(|list| list.ranked_keyword).unwrap_or_default();
+        let mut rising = lists.next().map(|list| list.ranked_keyword).unwrap_or_default();
+
+        // "Breakout" is localized, but it is the only Rising value not expressed as a percentage
+        for keyword in &mut rising {
+            keyword.is_breakout = !keyword.formatted_value.contains('%');
+        }
+
+        Self { top, rising }
+    }
+}
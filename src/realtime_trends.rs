@@ -0,0 +1,324 @@
+//! Represent Google Trend Realtime Search Trends.
+//!
+//! Stories trending right now for a given country, built from the searches and news articles of the past 24 hours.
+//! Results can be filtered by category.
+
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+use crate::daily_trends::{Article, Image};
+use crate::errors::Result;
+use crate::transport::Transport;
+use crate::explore::{ExploreResponse, Widget};
+use crate::request_handler::Query;
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+use crate::search_interest::SearchInterestResponse;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::{Client, SearchInterest};
+
+/// Category of the realtime stories.
+///
+/// # Example
+/// ```
+/// # use rtrend::realtime_trends::RealtimeCategory;
+/// let category = RealtimeCategory::SciTech;
+/// assert_eq!(category.to_string(), "t");
+/// ```
+#[derive(PartialEq, Display, Debug, EnumString, Clone, Copy, Default)]
+pub enum RealtimeCategory {
+    #[default]
+    #[strum(serialize = "all")]
+    All,
+    #[strum(serialize = "b")]
+    Business,
+    #[strum(serialize = "e")]
+    Entertainment,
+    #[strum(serialize = "m")]
+    Health,
+    #[strum(serialize = "t")]
+    SciTech,
+    #[strum(serialize = "s")]
+    Sports,
+    #[strum(serialize = "h")]
+    TopStories,
+}
+
+/// Realtime trending stories.
+///
+/// # Example
+/// ```
+/// # use rtrend::realtime_trends::RealtimeTrendsResponse;
+/// let body = r#"{
+///     "trendingStoryIds": ["US_lnk_abc"],
+///     "storySummaries": {"trendingStories": [{
+///         "id": "US_lnk_abc",
+///         "title": "Rust, Ferris",
+///         "entityNames": ["Rust", "Ferris"],
+///         "articles": [{"articleTitle": "Rust 2.0", "url": "https://example.com", "source": "Example", "time": "1 hour ago", "snippet": "..."}],
+///         "image": {"newsUrl": "https://example.com", "source": "Example", "imgUrl": "https://example.com/image.jpg"},
+///         "shareUrl": "https://trends.google.com/trends/trendingsearches/realtime?id=US_lnk_abc"
+///     }]}
+/// }"#;
+///
+/// let response: RealtimeTrendsResponse = serde_json::from_str(body).unwrap();
+/// let story = &response.stories[0];
+///
+/// assert_eq!(story.id, "US_lnk_abc");
+/// assert_eq!(story.entity_names, vec!["Rust", "Ferris"]);
+/// assert_eq!(story.articles[0].title, "Rust 2.0");
+/// assert_eq!(story.articles[0].time_ago, "1 hour ago");
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "RawRealtimeTrends")]
+pub struct RealtimeTrendsResponse {
+	/// Ids of all the trending stories, only the first ones come with a summary.
+	pub trending_story_ids: Vec<CompactString>,
+	pub stories: Vec<Story>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRealtimeTrends {
+	#[serde(default)]
+	trending_story_ids: Vec<CompactString>,
+	story_summaries: StorySummaries
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StorySummaries {
+	#[serde(default)]
+	trending_stories: Vec<Story>
+}
+
+/// Summary of a trending story.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Story {
+	/// Story id, to use with [`RealtimeTrends::story`].
+	pub id: CompactString,
+	pub title: CompactString,
+	#[serde(default)]
+	pub entity_names: Vec<CompactString>,
+	/// News articles clustered in this story.
+	#[serde(default)]
+	pub articles: Vec<Article>,
+	pub image: Option<Image>,
+	#[serde(default)]
+	pub share_url: CompactString
+}
+
+/// Details of a single story.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoryDetails {
+	#[serde(default)]
+	pub title: CompactString,
+	/// Widgets of the story page, the `TIMESERIES` widget holds the story timeline.
+	#[serde(default)]
+	pub widgets: Vec<Widget>
+}
+
+impl From<RawRealtimeTrends> for RealtimeTrendsResponse {
+    fn from(raw: RawRealtimeTrends) -> Self {
+        Self {
+            trending_story_ids: raw.trending_story_ids,
+            stories: raw.story_summaries.trending_stories,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RealtimeTrends<C = Client> {
+    pub client: C,
+    pub category: RealtimeCategory,
+}
+
+// Retrieve a single story, used by `RealtimeTrends::story`
+#[derive(Clone, Debug)]
+pub(crate) struct RealtimeStory<C = Client> {
+    pub(crate) client: C,
+    pub(crate) id: CompactString,
+}
+
+impl<C> RealtimeTrends<Client<C>> {
+    /// Create a `RealtimeTrends` instance for the country of the client.
+    ///
+    /// The client does not need keywords nor to be built.
+    ///
+    /// Returns a `RealtimeTrends` instance
+    pub fn new(client: Client<C>) -> Self {
+        Self {
+            client,
+            category: RealtimeCategory::All,
+        }
+    }
+
+    /// Only retrieve the stories of a category.
+    ///
+    /// By default, stories of all categories are retrieved.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RealtimeTrends};
+    /// # use rtrend::realtime_trends::RealtimeCategory;
+    /// let client = Client::new(Keywords::default(), Country::US);
+    ///
+    /// let stories = RealtimeTrends::new(client)
+    ///     .with_category(RealtimeCategory::Sports)
+    ///     .get();
+    /// ```
+    pub fn with_category(mut self, category: RealtimeCategory) -> Self {
+        self.category = category;
+        self
+    }
+}
+
+impl<C: Clone> RealtimeTrends<Client<C>> {
+    fn story_query(&self, id: &str) -> RealtimeStory<Client<C>> {
+        RealtimeStory {
+            client: self.client.clone(),
+            id: id.into(),
+        }
+    }
+
+    // The story widgets take the place of the explore widgets
+    fn story_client(&self, story: StoryDetails) -> Client<C> {
+        let mut client = self.client.clone();
+        client.response = ExploreResponse {
+            widgets: story.widgets,
+        };
+        client
+    }
+}
+
+impl<T: Transport> RealtimeTrends<Client<T>> {
+    /// Retrieve the realtime trending stories.
+    ///
+    /// Returns a `RealtimeTrendsResponse`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RealtimeTrends};
+    /// let client = Client::new(Keywords::default(), Country::US);
+    ///
+    /// for story in RealtimeTrends::new(client).get().stories {
+    ///     println!("{} [{}]", story.title, story.id);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the country of the client is `Country::ALL` or if the request fails, see [`RealtimeTrends::try_get`].
+    pub fn get(&self) -> RealtimeTrendsResponse {
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the realtime trending stories without panicking.
+    ///
+    /// Returns an `Error::CountryRequired` if the country of the client is `Country::ALL`.
+    pub fn try_get(&self) -> Result<RealtimeTrendsResponse> {
+        Ok(self.send_request(None)?.remove(0))
+    }
+
+    /// Retrieve the details of a single story.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RealtimeTrends};
+    /// let client = Client::new(Keywords::default(), Country::US);
+    /// let realtime_trends = RealtimeTrends::new(client);
+    ///
+    /// let story = &realtime_trends.get().stories[0];
+    /// let details = realtime_trends.story(&story.id);
+    ///
+    /// println!("{}", details.title);
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the request fails, see [`RealtimeTrends::try_story`].
+    pub fn story(&self, id: &str) -> StoryDetails {
+        self.try_story(id)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the details of a single story without panicking.
+    ///
+    /// The id is percent-encoded in the path of the request.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::{ClientBuilder, Country, RealtimeTrends};
+    /// # use rtrend::transport::Fixtures;
+    /// let fixtures = Fixtures::new().with("stories/US_lnk%3Fa%2Fb", r#")]}',{"title": "Rust", "widgets": []}"#);
+    /// let client = ClientBuilder::new().with_country(Country::US).build_with(fixtures.clone()).unwrap();
+    ///
+    /// let details = RealtimeTrends::new(client).try_story("US_lnk?a/b").unwrap();
+    ///
+    /// assert_eq!(details.title, "Rust");
+    /// assert_eq!(fixtures.requests()[0].path(), "/trends/api/stories/US_lnk%3Fa%2Fb");
+    /// ```
+    pub fn try_story(&self, id: &str) -> Result<StoryDetails> {
+        Ok(self.story_query(id).send_request(None)?.remove(0))
+    }
+
+    /// Retrieve the search interest timeline of a single story.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RealtimeTrends};
+    /// let client = Client::new(Keywords::default(), Country::US);
+    /// let realtime_trends = RealtimeTrends::new(client);
+    ///
+    /// let story = &realtime_trends.get().stories[0];
+    ///
+    /// for point in realtime_trends.story_timeline(&story.id).timeline_data {
+    ///     println!("{} {:?}", point.formatted_time, point.value);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the request fails, see [`RealtimeTrends::try_story_timeline`].
+    pub fn story_timeline(&self, id: &str) -> SearchInterestResponse {
+        self.try_story_timeline(id)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the search interest timeline of a single story without panicking.
+    ///
+    /// Returns an `Error::MissingWidget` if the story has no timeline.
+    pub fn try_story_timeline(&self, id: &str) -> Result<SearchInterestResponse> {
+        let story = self.try_story(id)?;
+        SearchInterest::new(self.story_client(story)).try_get()
+    }
+}
+
+#[cfg(feature = "async")]
+impl RealtimeTrends<AsyncClient> {
+    /// Asynchronously retrieve the realtime trending stories.
+    ///
+    /// # Panics
+    /// Panic if the country of the client is `Country::ALL` or if the request fails.
+    pub async fn get(&self) -> RealtimeTrendsResponse {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve the realtime trending stories without panicking.
+    pub async fn try_get(&self) -> Result<RealtimeTrendsResponse> {
+        Ok(self.send_request(None).await?.remove(0))
+    }
+
+    /// Asynchronously retrieve the details of a single story without panicking.
+    pub async fn try_story(&self, id: &str) -> Result<StoryDetails> {
+        Ok(self.story_query(id).send_request(None).await?.remove(0))
+    }
+
+    /// Asynchronously retrieve the search interest timeline of a single story without panicking.
+    pub async fn try_story_timeline(&self, id: &str) -> Result<SearchInterestResponse> {
+        let story = self.try_story(id).await?;
+        SearchInterest::new(self.story_client(story)).try_get().await
+    }
+}
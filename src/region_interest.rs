@@ -8,9 +8,14 @@ use compact_str::CompactString;
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::errors::KeywordNotSet;
+use crate::errors::Result;
+use crate::transport::Transport;
 use crate::request_handler::Query;
-use crate::{Client, Country};
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::{Client, Resolution};
 
 // Correpond to Multiline request => Google trend interest curve
 
@@ -43,99 +48,131 @@ pub struct Coordinates {
 }
 
 #[derive(Debug, Clone)]
-pub struct RegionInterest {
-    pub client: Client,
-    pub resolution: &'static str,
+pub struct RegionInterest<C = Client> {
+    pub client: C,
+    pub resolution: Resolution,
+    pub include_low_search_volume: bool,
 }
 
 impl Default for RegionInterest {
     fn default() -> Self {
-        Self {
-            client: Client::default(),
-            resolution: "REGION",
-        }
+        Self::new(Client::default())
     }
 }
 
-impl RegionInterest {
+impl<C> RegionInterest<Client<C>> {
     /// Create a `RegionInterest` Instance.
     ///
+    /// The resolution is `Resolution::Country` for all the countries and `Resolution::Region` otherwise.
+    ///
     /// Returns a `RegionInterest` instance
-    pub fn new(client: Client) -> Self {
-        let res = match client.country {
-            Country::ALL => "COUNTRY",
-            _ => "REGION"
-        };
+    pub fn new(client: Client<C>) -> Self {
+        let resolution = Resolution::default_for(&client.country);
 
         Self {
             client,
-            resolution: res,
+            resolution,
+            include_low_search_volume: false,
         }
     }
 
     /// Add a geographic filter.
-    /// You can filter result by "REGION" and "CITY".
+    /// You can filter result by [`Resolution`]: `Country`, `Region`, `Metro` (DMA) and `City`.
     ///
-    /// Warning : When making a request on all countries, use "COUNTRY" instead of "REGION" else it will panic
+    /// The resolution is checked against the country of the client and the geo of every comparison item
+    /// before any request is sent, see [`Resolution::check`].
     ///
     /// Returns a `RegionInterest` instance.
     ///
     /// # Example
-    /// ```
-    /// # use rtrend::{Country, Keywords, Client, RegionInterest};
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RegionInterest, Resolution};
     /// let keywords = Keywords::new(vec!["hacker"]);
     /// let country = Country::US;
     /// let client = Client::new(keywords, country).build();
     ///
-    /// let region_interest = RegionInterest::new(client).with_filter("CITY").get();
+    /// let region_interest = RegionInterest::new(client).with_filter(Resolution::Metro).get();
     ///
-    /// println!("{}", region_interest);
+    /// println!("{:#?}", region_interest);
     /// ```
     ///
-    /// # Panics
-    /// By default, on google trend, when making request on all countries, the country are called region (when you use filter).
-    /// But we can't use the keyword REGION to filter by COUNTRY. So instead use the keyword "COUNTRY"
-    ///
-    /// This example will panic
-    /// ```should_panic
-    /// # use rtrend::{Country, Keywords, Client, RegionInterest};
-    /// let keywords = Keywords::new(vec!["hacker"]);
-    /// let country = Country::ALL;
-    /// let client = Client::new(keywords, country).build();
+    /// On all the countries, the regions are the countries, `Resolution::Region` is rejected:
+    /// ```
+    /// # use rtrend::{Client, Country, Error, Keywords, RegionInterest, Resolution};
+    /// let client = Client::default().with_keywords(Keywords::new(vec!["hacker"]));
+    /// assert_eq!(client.country, Country::ALL);
     ///
-    /// let region_interest = RegionInterest::new(client).with_filter("REGION").get();
+    /// let region_interest = RegionInterest::new(client).with_filter(Resolution::Region).try_get();
     ///
-    /// println!("{}", region_interest);
+    /// assert!(matches!(region_interest, Err(Error::InvalidResolution(_))));
     /// ```
     ///
-    /// Instead do not filter and let the default value or use the "COUNTRY" filter
+    /// The metro areas are only available in the United States, for every item of a comparison:
     /// ```
-    /// # use rtrend::{Country, Keywords, Client, RegionInterest};
-    /// let keywords = Keywords::new(vec!["hacker"]);
-    /// let country = Country::ALL;
-    /// let client = Client::new(keywords, country).build();
+    /// # use rtrend::{ClientBuilder, ComparisonItem, Country, Error, RegionInterest, Resolution};
+    /// # use rtrend::transport::Fixtures;
+    /// let client = ClientBuilder::new()
+    ///     .with_country(Country::US)
+    ///     .with_comparison(vec![
+    ///         ComparisonItem::new("hacker"),
+    ///         ComparisonItem::new("hacker").with_geo(Country::DE),
+    ///     ])
+    ///     .build_with(Fixtures::new())
+    ///     .unwrap();
+    ///
+    /// let region_interest = RegionInterest::new(client).with_filter(Resolution::Metro).try_get();
+    ///
+    /// assert!(matches!(region_interest, Err(Error::InvalidResolution(_))));
+    /// ```
+    pub fn with_filter(mut self, resolution: Resolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Include the locations with a low search volume, they are left out by default.
     ///
-    /// let region_interest = RegionInterest::new(client).with_filter("COUNTRY").get();
-    /// // or
-    /// // let region_interest = RegionInterest::new(client).get();
-    ///  // will return the same result
+    /// Returns a `RegionInterest` instance.
     ///
-    ///  println!("{}", region_interest);
+    /// # Example
     /// ```
-    ///
-    pub fn with_filter(mut self, scale: &'static str) -> Self {
-        self.resolution = scale;
+    /// # use rtrend::{ClientBuilder, Country, Keywords, RegionInterest, Resolution};
+    /// # use rtrend::transport::Fixtures;
+    /// let fixtures = Fixtures::new()
+    ///     .with("explore", r#")]}'{"widgets": [{"id": "GEO_MAP", "token": "t", "request": {"resolution": "REGION"}}]}"#)
+    ///     .with("widgetdata/comparedgeo", r#")]}',{"default": {"geoMapData": []}}"#);
+    /// let client = ClientBuilder::new()
+    ///     .with_keywords(Keywords::new(vec!["hacker"]))
+    ///     .with_country(Country::US)
+    ///     .build_with(fixtures.clone())
+    ///     .unwrap()
+    ///     .try_build()
+    ///     .unwrap();
+    ///
+    /// RegionInterest::new(client)
+    ///     .with_filter(Resolution::City)
+    ///     .with_low_search_volume(true)
+    ///     .try_get()
+    ///     .unwrap();
+    ///
+    /// let request = fixtures.requests()[1].query_pairs().find(|(key, _)| key == "req").unwrap().1.into_owned();
+    /// assert!(request.contains(r#""resolution":"CITY""#));
+    /// assert!(request.contains(r#""includeLowSearchVolumeGeos":true"#));
+    /// ```
+    pub fn with_low_search_volume(mut self, include: bool) -> Self {
+        self.include_low_search_volume = include;
         self
     }
+}
 
+impl<T: Transport> RegionInterest<Client<T>> {
     /// Retrieve maps data for all keywords.
     ///
     /// Retrieve data for all keywords set within the client.
     ///
-    /// Returns a JSON serde Value (`serde_json::Value`).
+    /// Returns one `InterestForRegion` per region, its `value` holding one entry per keyword, in the order of the keywords.
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,no_run
     /// # use rtrend::{Country, Keywords, Client, RegionInterest};
     /// let keywords = Keywords::new(vec!["hacker"]);
     /// let country = Country::US;
@@ -143,7 +180,7 @@ impl RegionInterest {
     ///
     /// let region_interest = RegionInterest::new(client).get();
     ///
-    /// println!("{}", region_interest);
+    /// println!("{:#?}", region_interest);
     /// ```
     ///
     /// # Panics
@@ -160,17 +197,39 @@ impl RegionInterest {
     /// let region_interest = RegionInterest::new(client).get();
     /// ```
     pub fn get(&self) -> Vec<InterestForRegion> {
-        self.send_request().remove(0).default.geo_map_data
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve maps data for all keywords without panicking.
+    ///
+    /// Returns one `InterestForRegion` per region or an `Error` if the client have not been built or if the request fails.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RegionInterest};
+    /// # fn main() -> Result<(), rtrend::Error> {
+    /// let keywords = Keywords::new(vec!["hacker"]);
+    /// let client = Client::try_new(keywords, Country::US)?.try_build()?;
+    ///
+    /// let region_interest = RegionInterest::new(client).try_get()?;
+    ///
+    /// println!("{:#?}", region_interest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_get(&self) -> Result<Vec<InterestForRegion>> {
+        Ok(self.send_request(None)?.remove(0).default.geo_map_data)
     }
 
     /// Retrieve maps data for a specific keywords.
     ///
     /// Retrieve the data for one keywords set within the client.
+    /// A topic keyword can be designated by its id or by its name.
     ///
-    /// Returns a JSON serde Value (`serde_json::Value`).
+    /// Returns one `InterestForRegion` per region, its `value` holding the interest for the keyword.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Country, Keywords, Client, RegionInterest};
     /// let keywords = Keywords::new(vec!["PS4","XBOX","PC"]);
     /// let country = Country::ALL;
@@ -179,7 +238,7 @@ impl RegionInterest {
     ///
     /// let region_interest = RegionInterest::new(client).get_for("PS4");
     ///
-    /// println!("{}", region_interest);
+    /// println!("{:#?}", region_interest);
     /// ```
     ///
     /// # Panics
@@ -195,20 +254,64 @@ impl RegionInterest {
     /// let region_interest = RegionInterest::new(client).get_for("WII");
     /// ```
     pub fn get_for(&self, keyword: &str) -> Vec<InterestForRegion> {
-        let index = self
-            .client
-            .keywords
-            .keywords
-            .iter()
-            .position(|&x| x == keyword);
+        self.try_get_for(keyword)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        let keyword_index = match index {
-            Some(k) => k,
-            None => Err(KeywordNotSet).unwrap(),
-        };
+    /// Retrieve maps data for a specific keywords without panicking.
+    ///
+    /// Returns one `InterestForRegion` per region,
+    /// or an `Error::KeywordNotSet` if input keyword have not been set previously for the client.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RegionInterest, Error};
+    /// let keywords = Keywords::new(vec!["PS4","XBOX","PC"]);
+    /// let client = Client::new(keywords, Country::ALL).build();
+    ///
+    /// match RegionInterest::new(client).try_get_for("WII") {
+    ///     Err(Error::KeywordNotSet(keyword)) => println!("{} is not set", keyword),
+    ///     other => println!("{:#?}", other),
+    /// }
+    /// ```
+    pub fn try_get_for(&self, keyword: &str) -> Result<Vec<InterestForRegion>> {
+        let keyword = self.client.keywords.resolve(keyword)?.value();
+
+        Ok(self.send_request(Some(keyword))?.remove(0).default.geo_map_data)
+    }
+}
+
+#[cfg(feature = "async")]
+impl RegionInterest<AsyncClient> {
+    /// Asynchronously retrieve maps data for all keywords.
+    ///
+    /// # Panics
+    /// Panic if the client have not been built or if the request fails.
+    pub async fn get(&self) -> Vec<InterestForRegion> {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve maps data for all keywords without panicking.
+    pub async fn try_get(&self) -> Result<Vec<InterestForRegion>> {
+        Ok(self.send_request(None).await?.remove(0).default.geo_map_data)
+    }
+
+    /// Asynchronously retrieve maps data for a specific keywords.
+    ///
+    /// # Panics
+    /// Will panic if input keyword have not been set previously for the client.
+    pub async fn get_for(&self, keyword: &str) -> Vec<InterestForRegion> {
+        self.try_get_for(keyword)
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        let response_index = keyword_index + 1;
+    /// Asynchronously retrieve maps data for a specific keywords without panicking.
+    pub async fn try_get_for(&self, keyword: &str) -> Result<Vec<InterestForRegion>> {
+        let keyword = self.client.keywords.resolve(keyword)?.value();
 
-        self.send_request().remove(response_index).default.geo_map_data
+        Ok(self.send_request(Some(keyword)).await?.remove(0).default.geo_map_data)
     }
 }
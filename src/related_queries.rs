@@ -3,37 +3,43 @@
 //! Users searching for your term also searched for these queries.
 //! You can sort by the following metrics:
 //! - Top - The most popular search queries.
-//! Scoring is on a relative scale where a value of 100 is the most commonly searched query, 50 is a query searched half as often as the most popular query, and so on.
+//!   Scoring is on a relative scale where a value of 100 is the most commonly searched query, 50 is a query searched half as often as the most popular query, and so on.
 //! - Rising - Queries with the biggest increase in search frequency since the last time period.
-//! Results marked "Breakout" had a tremendous increase, probably because these queries are new and had few (if any) prior searches.
+//!   Results marked "Breakout" had a tremendous increase, probably because these queries are new and had few (if any) prior searches.
 
-use crate::errors::KeywordNotSet;
+use crate::errors::Result;
+use crate::transport::Transport;
+use crate::ranked_list::{RankedKeyword, RankedList};
 use crate::request_handler::Query;
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
 use crate::Client;
 
-use serde_json::Value;
-
 #[derive(Clone, Debug, Default)]
-pub struct RelatedQueries {
-    pub client: Client,
+pub struct RelatedQueries<C = Client> {
+    pub client: C,
 }
 
-impl RelatedQueries {
+impl<C> RelatedQueries<Client<C>> {
     /// Create a `RelatedQueries` Instance.
     /// 
     /// Returns a `RelatedQueries` instance
-    pub fn new(client: Client) -> Self {
+    pub fn new(client: Client<C>) -> Self {
         Self { client }
     }
+}
 
+impl<T: Transport> RelatedQueries<Client<T>> {
     /// Retrieve Queries data for all keywords.
     ///
     /// Retrieve data for all keywords set within the client.
     ///
-    /// Returns a JSON serde Value (`serde_json::Value`).
+    /// Returns one `RankedList` per keyword, in the order of the keywords.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Country, Keywords, Client, RelatedQueries};
     /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
     /// let country = Country::ALL;
@@ -41,7 +47,7 @@ impl RelatedQueries {
     ///
     /// let related_queries = RelatedQueries::new(client).get();
     ///
-    /// println!("{}", related_queries);
+    /// println!("{:#?}", related_queries);
     /// ```
     ///
     /// # Panics
@@ -51,65 +57,160 @@ impl RelatedQueries {
     /// # use rtrend::{Country, Keywords, Client, RelatedQueries};
     /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
     /// let country = Country::ALL;
+    ///
+    /// // Client not built
     /// let client = Client::new(keywords, country);
     ///
     /// let related_queries = RelatedQueries::new(client).get();
     /// ```
-    pub fn get(&self) -> Value {
-        let value = self
-            .send_request()
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>();
-        let joined = value.join(",");
+    pub fn get(&self) -> Vec<RankedList> {
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve Queries data for all keywords without panicking.
+    ///
+    /// Returns one `RankedList` per keyword or an `Error` if the client have not been built or if the request fails.
+    pub fn try_get(&self) -> Result<Vec<RankedList>> {
+        Ok(self.send_request(None)?.into_iter().map(RankedList::from).collect())
+    }
+
+    /// Retrieve Top Queries for all keywords, in descending order.
+    ///
+    /// Returns one list per keyword, in the order of the keywords.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RelatedQueries};
+    /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
+    /// let country = Country::ALL;
+    /// let client = Client::new(keywords, country).build();
+    ///
+    /// let related_queries = RelatedQueries::new(client).top();
+    ///
+    /// println!("{:#?}", related_queries);
+    /// ```
+    pub fn top(&self) -> Vec<Vec<RankedKeyword>> {
+        self.try_top().unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        let form: String = format!("[{}]", joined);
+    /// Retrieve Top Queries for all keywords without panicking.
+    pub fn try_top(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get()?.into_iter().map(|list| list.top).collect())
+    }
+
+    /// Retrieve Rising Queries for all keywords, in descending order.
+    ///
+    /// Returns one list per keyword, in the order of the keywords.
+    /// "Breakout" entries are flagged with `is_breakout`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, RelatedQueries};
+    /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
+    /// let country = Country::ALL;
+    /// let client = Client::new(keywords, country).build();
+    ///
+    /// for keyword in RelatedQueries::new(client).rising().remove(0) {
+    ///     if keyword.is_breakout {
+    ///         println!("Breakout: {:?}", keyword.item);
+    ///     }
+    /// }
+    /// ```
+    pub fn rising(&self) -> Vec<Vec<RankedKeyword>> {
+        self.try_rising().unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        serde_json::from_str(form.as_str()).unwrap()
+    /// Retrieve Rising Queries for all keywords without panicking.
+    pub fn try_rising(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get()?.into_iter().map(|list| list.rising).collect())
     }
 
     /// Retrieve Queries data for a specific keywords.
     ///
     /// Retrieve data for a specific keyword set within the client.
+    /// A topic keyword can be designated by its id or by its name.
     ///
-    /// Returns a JSON serde Value (`serde_json::Value`).
+    /// Returns a `RankedList`.
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use rtrend::{Country, Keywords, Client, RelatedQueries};
     /// let keywords = Keywords::new(vec!["Github", "Gitlab"]);
     /// let country = Country::ALL;
-    ///
     /// let client = Client::new(keywords, country).build();
     ///
     /// let related_queries = RelatedQueries::new(client).get_for("Gitlab");
     ///
-    /// println!("{}", related_queries);
+    /// println!("{:#?}", related_queries.top);
     /// ```
-    /// 
+    ///
     /// # Panics
     /// Will panic if input keyword have not been set previously for the client.
-    /// 
+    ///
     /// ```should_panic
     /// # use rtrend::{Country, Keywords, Client, RelatedQueries};
     /// let keywords = Keywords::new(vec!["PS4","XBOX","PC"]);
     /// let country = Country::ALL;
-    /// 
+    ///
     /// let client = Client::new(keywords, country).build();
-    /// 
-    /// let region_interest = RelatedQueries::new(client).get_for("WII");
+    ///
+    /// let related_queries = RelatedQueries::new(client).get_for("WII");
     /// ```
-    pub fn get_for(&self, keyword: &str) -> Value {
-        let index = self
-            .client
-            .keywords
-            .keywords
-            .iter()
-            .position(|&x| x == keyword);
-        let keyword_index = match index {
-            Some(k) => k,
-            None => Err(KeywordNotSet).unwrap(),
-        };
-
-        self.send_request()[keyword_index].clone()
+    pub fn get_for(&self, keyword: &str) -> RankedList {
+        self.try_get_for(keyword)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve Queries data for a specific keywords without panicking.
+    ///
+    /// Returns an `Error::KeywordNotSet` if input keyword have not been set previously for the client.
+    pub fn try_get_for(&self, keyword: &str) -> Result<RankedList> {
+        let keyword = self.client.keywords.resolve(keyword)?.value();
+
+        Ok(self.send_request(Some(keyword))?.remove(0).into())
+    }
+}
+
+#[cfg(feature = "async")]
+impl RelatedQueries<AsyncClient> {
+    /// Asynchronously retrieve Queries data for all keywords.
+    ///
+    /// # Panics
+    /// Panic if the client have not been built or if the request fails.
+    pub async fn get(&self) -> Vec<RankedList> {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve Queries data for all keywords without panicking.
+    pub async fn try_get(&self) -> Result<Vec<RankedList>> {
+        Ok(self.send_request(None).await?.into_iter().map(RankedList::from).collect())
+    }
+
+    /// Asynchronously retrieve Top Queries for all keywords without panicking.
+    pub async fn try_top(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get().await?.into_iter().map(|list| list.top).collect())
+    }
+
+    /// Asynchronously retrieve Rising Queries for all keywords without panicking.
+    pub async fn try_rising(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get().await?.into_iter().map(|list| list.rising).collect())
+    }
+
+    /// Asynchronously retrieve Queries data for a specific keywords.
+    ///
+    /// # Panics
+    /// Will panic if input keyword have not been set previously for the client.
+    pub async fn get_for(&self, keyword: &str) -> RankedList {
+        self.try_get_for(keyword)
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve Queries data for a specific keywords without panicking.
+    pub async fn try_get_for(&self, keyword: &str) -> Result<RankedList> {
+        let keyword = self.client.keywords.resolve(keyword)?.value();
+
+        Ok(self.send_request(Some(keyword)).await?.remove(0).into())
     }
 }
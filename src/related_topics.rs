@@ -3,35 +3,44 @@
 //! Users searching for your keywords also searched for these topics.
 //! You can view by the following metrics:
 //! - Top - The most popular topics.
-//! Scoring is on a relative scale where a value of 100 is the most commonly searched topic and a value of 50 is a topic searched half as often as the most popular term, and so on.
+//!   Scoring is on a relative scale where a value of 100 is the most commonly searched topic and a value of 50 is a topic searched half as often as the most popular term, and so on.
 //! - Rising
-//! Related topics with the biggest increase in search frequency since the last time period.
-//! Results marked "Breakout" had a tremendous increase, probably because these topics are new and had few (if any) prior searches.
+//!   Related topics with the biggest increase in search frequency since the last time period.
+//!   Results marked "Breakout" had a tremendous increase, probably because these topics are new and had few (if any) prior searches.
 
-use crate::errors::KeywordNotSet;
+use crate::errors::Result;
+use crate::transport::Transport;
+use crate::ranked_list::{RankedKeyword, RankedList};
 use crate::request_handler::Query;
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
 use crate::Client;
-use serde_json::Value;
 
 #[derive(Clone, Debug, Default)]
-pub struct RelatedTopics {
-    pub client: Client,
+pub struct RelatedTopics<C = Client> {
+    pub client: C,
 }
 
-impl RelatedTopics {
+impl<C> RelatedTopics<Client<C>> {
     /// Create a `RelatedTopics` Instance.
     /// 
     /// Returns a `RelatedTopics` instance
-    pub fn new(client: Client) -> Self {
+    pub fn new(client: Client<C>) -> Self {
         Self { client }
     }
+}
 
-    /// Retrieve Topics data for all keywords set within the client.
+impl<T: Transport> RelatedTopics<Client<T>> {
+    /// Retrieve Topics data for all keywords.
+    ///
+    /// Retrieve data for all keywords set within the client.
     ///
-    /// Returns a `serde_json::Value`.
+    /// Returns one `RankedList` per keyword, in the order of the keywords.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Country, Keywords, Client, RelatedTopics};
     /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
     /// let country = Country::ALL;
@@ -39,7 +48,7 @@ impl RelatedTopics {
     ///
     /// let related_topics = RelatedTopics::new(client).get();
     ///
-    /// println!("{}", related_topics);
+    /// println!("{:#?}", related_topics);
     /// ```
     ///
     /// # Panics
@@ -47,31 +56,31 @@ impl RelatedTopics {
     ///
     /// ```should_panic
     /// # use rtrend::{Country, Keywords, Client, RelatedTopics};
-    /// let keywords = Keywords::new(vec!["hacker"]);
-    /// let country = Country::US;
+    /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
+    /// let country = Country::ALL;
     ///
     /// // Client not built
     /// let client = Client::new(keywords, country);
     ///
     /// let related_topics = RelatedTopics::new(client).get();
     /// ```
-    pub fn get(&self) -> Value {
-        let value = self
-            .send_request()
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>();
-        let joined = value.join(",");
-        let form: String = format!("[{}]", joined);
-
-        serde_json::from_str(form.as_str()).unwrap()
+    pub fn get(&self) -> Vec<RankedList> {
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
     }
 
-    /// Retrieve Topics data for all keywords filtered by Top Topics in descending order
-    /// Returns a `serde_json::Value`.
-    /// 
+    /// Retrieve Topics data for all keywords without panicking.
+    ///
+    /// Returns one `RankedList` per keyword or an `Error` if the client have not been built or if the request fails.
+    pub fn try_get(&self) -> Result<Vec<RankedList>> {
+        Ok(self.send_request(None)?.into_iter().map(RankedList::from).collect())
+    }
+
+    /// Retrieve Top Topics for all keywords, in descending order.
+    ///
+    /// Returns one list per keyword, in the order of the keywords.
+    ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Country, Keywords, Client, RelatedTopics};
     /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
     /// let country = Country::ALL;
@@ -79,42 +88,52 @@ impl RelatedTopics {
     ///
     /// let related_topics = RelatedTopics::new(client).top();
     ///
-    /// println!("{}", related_topics);
+    /// println!("{:#?}", related_topics);
     /// ```
-    pub fn top(&self) -> Value {
-        self.get()[0].clone()
+    pub fn top(&self) -> Vec<Vec<RankedKeyword>> {
+        self.try_top().unwrap_or_else(|error| panic!("{}", error))
     }
 
-    /// Retrieve Topics data for all keywords filtered by Rising Topics in descending order
-    /// Returns a `serde_json::Value`.
-    /// 
-    /// # Example
-    /// Retrieve Topics data for all keywords filtered by Top Topics in descending order
-    /// Returns a `serde_json::Value`.
-    /// 
+    /// Retrieve Top Topics for all keywords without panicking.
+    pub fn try_top(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get()?.into_iter().map(|list| list.top).collect())
+    }
+
+    /// Retrieve Rising Topics for all keywords, in descending order.
+    ///
+    /// Returns one list per keyword, in the order of the keywords.
+    /// "Breakout" entries are flagged with `is_breakout`.
+    ///
     /// # Example
-    /// ```
+    /// ```no_run
     /// # use rtrend::{Country, Keywords, Client, RelatedTopics};
     /// let keywords = Keywords::new(vec!["Github vs Gitlab"]);
     /// let country = Country::ALL;
     /// let client = Client::new(keywords, country).build();
     ///
-    /// let related_topics = RelatedTopics::new(client).rising();
-    ///
-    /// println!("{}", related_topics);
+    /// for keyword in RelatedTopics::new(client).rising().remove(0) {
+    ///     if keyword.is_breakout {
+    ///         println!("Breakout: {:?}", keyword.item);
+    ///     }
+    /// }
     /// ```
-    pub fn rising(&self) -> Value {
-        self.get()[1].clone()
+    pub fn rising(&self) -> Vec<Vec<RankedKeyword>> {
+        self.try_rising().unwrap_or_else(|error| panic!("{}", error))
     }
 
+    /// Retrieve Rising Topics for all keywords without panicking.
+    pub fn try_rising(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get()?.into_iter().map(|list| list.rising).collect())
+    }
 
     /// Retrieve Topics data for a specific keywords.
     ///
     /// Retrieve data for a specific keyword set within the client.
+    /// A topic keyword can be designated by its id or by its name.
     ///
-    /// Returns a JSON serde Value (`serde_json::Value`).
+    /// Returns a `RankedList`.
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use rtrend::{Country, Keywords, Client, RelatedTopics};
     /// let keywords = Keywords::new(vec!["Github", "Gitlab"]);
     /// let country = Country::ALL;
@@ -122,8 +141,9 @@ impl RelatedTopics {
     ///
     /// let related_topics = RelatedTopics::new(client).get_for("Gitlab");
     ///
-    /// println!("{}", related_topics);
+    /// println!("{:#?}", related_topics.top);
     /// ```
+    ///
     /// # Panics
     /// Will panic if input keyword have not been set previously for the client.
     ///
@@ -134,20 +154,64 @@ impl RelatedTopics {
     ///
     /// let client = Client::new(keywords, country).build();
     ///
-    /// let region_interest = RelatedTopics::new(client).get_for("WII");
+    /// let related_topics = RelatedTopics::new(client).get_for("WII");
     /// ```
-    pub fn get_for(&self, keyword: &str) -> Value {
-        let index = self
-            .client
-            .keywords
-            .keywords
-            .iter()
-            .position(|&x| x == keyword);
-        let keyword_index = match index {
-            Some(k) => k,
-            None => Err(KeywordNotSet).unwrap(),
-        };
-
-        self.send_request()[keyword_index].clone()
+    pub fn get_for(&self, keyword: &str) -> RankedList {
+        self.try_get_for(keyword)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve Topics data for a specific keywords without panicking.
+    ///
+    /// Returns an `Error::KeywordNotSet` if input keyword have not been set previously for the client.
+    pub fn try_get_for(&self, keyword: &str) -> Result<RankedList> {
+        let keyword = self.client.keywords.resolve(keyword)?.value();
+
+        Ok(self.send_request(Some(keyword))?.remove(0).into())
+    }
+}
+
+#[cfg(feature = "async")]
+impl RelatedTopics<AsyncClient> {
+    /// Asynchronously retrieve Topics data for all keywords.
+    ///
+    /// # Panics
+    /// Panic if the client have not been built or if the request fails.
+    pub async fn get(&self) -> Vec<RankedList> {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve Topics data for all keywords without panicking.
+    pub async fn try_get(&self) -> Result<Vec<RankedList>> {
+        Ok(self.send_request(None).await?.into_iter().map(RankedList::from).collect())
+    }
+
+    /// Asynchronously retrieve Top Topics for all keywords without panicking.
+    pub async fn try_top(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get().await?.into_iter().map(|list| list.top).collect())
+    }
+
+    /// Asynchronously retrieve Rising Topics for all keywords without panicking.
+    pub async fn try_rising(&self) -> Result<Vec<Vec<RankedKeyword>>> {
+        Ok(self.try_get().await?.into_iter().map(|list| list.rising).collect())
+    }
+
+    /// Asynchronously retrieve Topics data for a specific keywords.
+    ///
+    /// # Panics
+    /// Will panic if input keyword have not been set previously for the client.
+    pub async fn get_for(&self, keyword: &str) -> RankedList {
+        self.try_get_for(keyword)
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve Topics data for a specific keywords without panicking.
+    pub async fn try_get_for(&self, keyword: &str) -> Result<RankedList> {
+        let keyword = self.client.keywords.resolve(keyword)?.value();
+
+        Ok(self.send_request(Some(keyword)).await?.remove(0).into())
     }
 }
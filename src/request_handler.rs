@@ -1,205 +1,440 @@
 use std::collections::HashMap;
 
+use crate::errors::{Error, Result};
+use crate::explore::Widget;
 use crate::{
-    utils, Client, Keywords, RegionInterest, RelatedQueries, RelatedTopics, SearchInterest,
+    utils, Client, Country, DailyTrends, RealtimeTrends, RegionInterest, RelatedQueries,
+    RelatedTopics, Resolution, SearchInterest, Suggestions,
 };
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::daily_trends::DailyTrendsEnvelope;
+use crate::ranked_list::RelatedSearchesResponse;
+use crate::realtime_trends::{RealtimeStory, RealtimeTrendsResponse, StoryDetails};
 use crate::region_interest::RegionInterestResponse;
-use reqwest::{blocking::RequestBuilder, Url};
+use crate::search_interest::MultilineResponse;
+use crate::suggestions::AutocompleteResponse;
+use crate::transport::Transport;
+use chrono::NaiveDate;
+use reqwest::Url;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
+const MULTILINE_ENDPOINT: &str = "https://trends.google.com/trends/api/widgetdata/multiline";
+const COMPAREDGEO_ENDPOINT: &str = "https://trends.google.com/trends/api/widgetdata/comparedgeo";
+const RELATED_SEARCH_ENDPOINT: &str =
+    "https://trends.google.com/trends/api/widgetdata/relatedsearches";
+const DAILY_TRENDS_ENDPOINT: &str = "https://trends.google.com/trends/api/dailytrends";
+const REALTIME_TRENDS_ENDPOINT: &str = "https://trends.google.com/trends/api/realtimetrends";
+const STORIES_ENDPOINT: &str = "https://trends.google.com/trends/api/stories/";
+const AUTOCOMPLETE_ENDPOINT: &str = "https://trends.google.com/trends/api/autocomplete/";
+
+const TIMESERIES: &str = "TIMESERIES";
+const GEO_MAP: &str = "GEO_MAP";
+const RELATED_TOPICS: &str = "RELATED_TOPICS";
+const RELATED_QUERIES: &str = "RELATED_QUERIES";
+
 pub trait Query {
 	type Result: DeserializeOwned;
-    // Build queries for all type of search
-    fn build_request(&self) -> Vec<RequestBuilder>;
+	type Transport: Transport;
+    // Build queries for all keywords, or only for `keyword` when set
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>>;
 
-	fn client(&self) -> &Client;
+	fn client(&self) -> &Client<Self::Transport>;
 
     // Send queries for request build previously
-    fn send_request(&self) -> Vec<Self::Result> {
-        const BAD_CHARACTER: usize = 5;
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "widget", skip_all, fields(keyword = ?keyword)))]
+    fn send_request(&self, keyword: Option<&str>) -> Result<Vec<Self::Result>> {
         let mut responses: Vec<Self::Result> = Vec::new();
 
-        for request in self.build_request() {
-			let req = request.build().unwrap();
-			eprintln!("{} {}", req.method(), req.url());
-			for (header, value) in req.headers().iter() {
-				eprintln!("  {}: {:?}", header, value);
-			}
-			if let Some(b) = req.body() {
-				eprintln!("  {:?}", b);
-			}
-
-            let resp = self.client().client.execute(req);
-            let resp = match resp {
-                Ok(resp) => resp,
-                Err(error) => panic!("Can't get client response: {:?}", error),
-            };
-            let body = resp.text().unwrap();
-			//eprintln!("{}", body);
-            let clean_response = utils::sanitize_response(&body, BAD_CHARACTER);
-			//eprintln!("{}", clean_response);
-            responses.push(serde_json::from_str(clean_response).unwrap());
+        for url in self.build_request(keyword)? {
+            let resp = self.client().fetch(&url)?;
+            responses.push(utils::parse_response(&resp.body)?);
         }
-        responses
+        Ok(responses)
     }
 }
 
-impl Query for SearchInterest {
-	type Result = Value;
-	fn client(&self) -> &Client {
+/// Asynchronous counterpart of [`Query`], available with the `async` feature.
+#[cfg(feature = "async")]
+pub trait AsyncQuery {
+	type Result: DeserializeOwned;
+    // Build queries for all keywords, or only for `keyword` when set
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>>;
+
+	fn client(&self) -> &AsyncClient;
+
+    // Send queries for request build previously
+    async fn send_request(&self, keyword: Option<&str>) -> Result<Vec<Self::Result>> {
+        let mut responses: Vec<Self::Result> = Vec::new();
+
+        for url in self.build_request(keyword)? {
+            let resp = self.client().fetch_async(&url).await?;
+            responses.push(utils::parse_response(&resp.body)?);
+        }
+        Ok(responses)
+    }
+}
+
+impl<T: Transport> Query for SearchInterest<Client<T>> {
+	type Result = MultilineResponse;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
 		&self.client
 	}
 
-    fn build_request(&self) -> Vec<RequestBuilder> {
-        const MULTILINE_ENDPOINT: &str =
-            "https://trends.google.com/trends/api/widgetdata/multiline";
-        let url = Url::parse(MULTILINE_ENDPOINT).unwrap();
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        search_interest_request(&self.client)
+    }
+}
+
+impl<T: Transport> Query for RegionInterest<Client<T>> {
+	type Result = RegionInterestResponse;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>> {
+        region_interest_request(&self.client, self.resolution, self.include_low_search_volume, keyword)
+    }
+}
+
+impl<T: Transport> Query for RelatedTopics<Client<T>> {
+	type Result = RelatedSearchesResponse;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>> {
+        related_search_request(&self.client, RELATED_TOPICS, keyword)
+    }
+}
+
+impl<T: Transport> Query for RelatedQueries<Client<T>> {
+	type Result = RelatedSearchesResponse;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>> {
+        related_search_request(&self.client, RELATED_QUERIES, keyword)
+    }
+}
+
+impl<T: Transport> Query for DailyTrends<Client<T>> {
+	type Result = DailyTrendsEnvelope;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        daily_trends_request(&self.client, self.date)
+    }
+}
+
+impl<T: Transport> Query for RealtimeTrends<Client<T>> {
+	type Result = RealtimeTrendsResponse;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        realtime_trends_request(&self.client, &self.category.to_string())
+    }
+}
+
+impl<T: Transport> Query for RealtimeStory<Client<T>> {
+	type Result = StoryDetails;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        Ok(vec![story_request(&self.client, &self.id)])
+    }
+}
+
+impl<T: Transport> Query for Suggestions<Client<T>> {
+	type Result = AutocompleteResponse;
+	type Transport = T;
+	fn client(&self) -> &Client<T> {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        Ok(vec![autocomplete_request(&self.client, &self.term)])
+    }
+}
 
-        let request = self.client.response["widgets"][0]["request"].to_string();
-        let token = self.client.response["widgets"][0]["token"]
-            .to_string()
-            .replace('\"', "");
+#[cfg(feature = "async")]
+impl AsyncQuery for SearchInterest<AsyncClient> {
+	type Result = MultilineResponse;
+	fn client(&self) -> &AsyncClient {
+		&self.client
+	}
 
-        vec![build_query(&self.client, url, request, token)]
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        search_interest_request(&self.client)
     }
 }
 
-impl Query for RegionInterest {
+#[cfg(feature = "async")]
+impl AsyncQuery for RegionInterest<AsyncClient> {
 	type Result = RegionInterestResponse;
-	fn client(&self) -> &Client {
-		&self.client
-	}
-
-    fn build_request(&self) -> Vec<RequestBuilder> {
-        const COMPAREDGEO_ENDPOINT: &str =
-            "https://trends.google.com/trends/api/widgetdata/comparedgeo";
-        let url = Url::parse(COMPAREDGEO_ENDPOINT).unwrap();
-        let keywords_nb = self.client.keywords.keywords.len();
-        let mut requests: Vec<RequestBuilder> = Vec::new();
-
-        if keywords_nb == 1 {
-            let request = self.client.response["widgets"][1]["request"].clone();
-            let mod_region_request = mod_region_request(request, self.resolution).to_string();
-			eprintln!("req: {}", mod_region_request);
-
-            let token = self.client.response["widgets"][1]["token"]
-                .to_string()
-                .replace('\"', "");
-
-            vec![build_query(&self.client, url, mod_region_request, token)]
-        } else {
-            for i in 1..=keywords_nb {
-                let request = self.client.response["widgets"][i * 3]["request"].clone();
-                let mod_region_request = mod_region_request(request, self.resolution).to_string();
-
-                let token = self.client.response["widgets"][i * 3]["token"]
-                    .to_string()
-                    .replace('\"', "");
-                requests.push(build_query(
-                    &self.client,
-                    url.clone(),
-                    mod_region_request,
-                    token,
-                ));
-            }
-
-            requests
-        }
+	fn client(&self) -> &AsyncClient {
+		&self.client
+	}
+
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>> {
+        region_interest_request(&self.client, self.resolution, self.include_low_search_volume, keyword)
     }
 }
 
-impl Query for RelatedTopics {
-	type Result = Value;
-	fn client(&self) -> &Client {
+#[cfg(feature = "async")]
+impl AsyncQuery for RelatedTopics<AsyncClient> {
+	type Result = RelatedSearchesResponse;
+	fn client(&self) -> &AsyncClient {
 		&self.client
 	}
 
-    fn build_request(&self) -> Vec<RequestBuilder> {
-        const RELATED_SEARCH_ENDPOINT: &str =
-            "https://trends.google.com/trends/api/widgetdata/relatedsearches";
-        let url = Url::parse(RELATED_SEARCH_ENDPOINT).unwrap();
-        let keywords = self.client.keywords.keywords.clone();
-        let mut requests: Vec<RequestBuilder> = Vec::new();
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>> {
+        related_search_request(&self.client, RELATED_TOPICS, keyword)
+    }
+}
 
-        if keywords.len() == 1 {
-            let request = self.client.response["widgets"][2]["request"].to_string();
-            let token = self.client.response["widgets"][2]["token"]
-                .to_string()
-                .replace('\"', "");
-            vec![build_query(&self.client, url, request, token)]
-        } else {
-            for keyword in &keywords {
-                let individual_keyword = Keywords::new(vec![keyword]);
+#[cfg(feature = "async")]
+impl AsyncQuery for RelatedQueries<AsyncClient> {
+	type Result = RelatedSearchesResponse;
+	fn client(&self) -> &AsyncClient {
+		&self.client
+	}
 
-                let new_client = self
-                    .client
-                    .clone()
-                    .with_keywords(individual_keyword)
-                    .build();
-                let request = new_client.response["widgets"][2]["request"].to_string();
-                let token = new_client.response["widgets"][2]["token"]
-                    .to_string()
-                    .replace('\"', "");
-                requests.push(build_query(&new_client, url.clone(), request, token));
-            }
+    fn build_request(&self, keyword: Option<&str>) -> Result<Vec<Url>> {
+        related_search_request(&self.client, RELATED_QUERIES, keyword)
+    }
+}
 
-            requests
-        }
+#[cfg(feature = "async")]
+impl AsyncQuery for DailyTrends<AsyncClient> {
+	type Result = DailyTrendsEnvelope;
+	fn client(&self) -> &AsyncClient {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        daily_trends_request(&self.client, self.date)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncQuery for RealtimeTrends<AsyncClient> {
+	type Result = RealtimeTrendsResponse;
+	fn client(&self) -> &AsyncClient {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        realtime_trends_request(&self.client, &self.category.to_string())
     }
 }
 
-impl Query for RelatedQueries {
-	type Result = Value;
-	fn client(&self) -> &Client {
+#[cfg(feature = "async")]
+impl AsyncQuery for RealtimeStory<AsyncClient> {
+	type Result = StoryDetails;
+	fn client(&self) -> &AsyncClient {
 		&self.client
 	}
 
-    fn build_request(&self) -> Vec<RequestBuilder> {
-        const RELATED_QUERY_ENDPOINT: &str =
-            "https://trends.google.com/trends/api/widgetdata/relatedsearches";
-        let url = Url::parse(RELATED_QUERY_ENDPOINT).unwrap();
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        Ok(vec![story_request(&self.client, &self.id)])
+    }
+}
 
-        let mut requests: Vec<RequestBuilder> = Vec::new();
-        let keywords_nb = self.client.keywords.keywords.len();
+#[cfg(feature = "async")]
+impl AsyncQuery for Suggestions<AsyncClient> {
+	type Result = AutocompleteResponse;
+	fn client(&self) -> &AsyncClient {
+		&self.client
+	}
+
+    fn build_request(&self, _keyword: Option<&str>) -> Result<Vec<Url>> {
+        Ok(vec![autocomplete_request(&self.client, &self.term)])
+    }
+}
 
-        if keywords_nb == 1 {
-            let request = self.client.response["widgets"][3]["request"].to_string();
-            let token = self.client.response["widgets"][3]["token"]
-                .to_string()
-                .replace('\"', "");
-            vec![build_query(&self.client, url, request, token)]
-        } else {
-            for i in 1..=keywords_nb {
-                let request = self.client.response["widgets"][i * 3 + 1]["request"].to_string();
-                let token = self.client.response["widgets"][i * 3 + 1]["token"]
-                    .to_string()
-                    .replace('\"', "");
-                requests.push(build_query(&self.client, url.clone(), request, token));
-            }
-            requests
+fn search_interest_request<C>(client: &Client<C>) -> Result<Vec<Url>> {
+    let widget = widget(client, TIMESERIES, None)?;
+
+    Ok(vec![build_query(client, MULTILINE_ENDPOINT, widget.request.to_string(), &widget.token)])
+}
+
+// Without keyword, the first geo widget compares all the keywords
+fn region_interest_request<C>(
+    client: &Client<C>,
+    resolution: Resolution,
+    include_low_search_volume: bool,
+    keyword: Option<&str>,
+) -> Result<Vec<Url>> {
+    resolution.check(&client.country)?;
+    for geo in client.comparison.iter().filter_map(|item| item.geo.as_ref()) {
+        resolution.check(geo)?;
+    }
+    let widget = widget(client, GEO_MAP, keyword)?;
+    let mod_region_request =
+        mod_region_request(&widget.request, resolution, include_low_search_volume)?.to_string();
+    trace_event!(trace, request = %mod_region_request, "comparedgeo request");
+
+    Ok(vec![build_query(client, COMPAREDGEO_ENDPOINT, mod_region_request, &widget.token)])
+}
+
+// Related searches widgets are dedicated to one keyword, without keyword one request is built per comparison item
+fn related_search_request<C>(client: &Client<C>, id: &str, keyword: Option<&str>) -> Result<Vec<Url>> {
+    let widgets = match keyword {
+        Some(keyword) => vec![widget(client, id, Some(keyword))?],
+        None => client
+            .keywords
+            .iter()
+            .enumerate()
+            .map(|(index, keyword)| item_widget(client, id, Some(index), keyword.value()))
+            .collect::<Result<Vec<&Widget>>>()?,
+    };
+
+    Ok(widgets
+        .into_iter()
+        .map(|widget| build_query(client, RELATED_SEARCH_ENDPOINT, widget.request.to_string(), &widget.token))
+        .collect())
+}
+
+// Daily trends are only available for a specific country
+fn daily_trends_request<C>(client: &Client<C>, date: Option<NaiveDate>) -> Result<Vec<Url>> {
+    if client.country == Country::ALL {
+        return Err(Error::CountryRequired);
+    }
+
+    let mut url = Url::parse_with_params(
+        DAILY_TRENDS_ENDPOINT,
+        &[
+            ("hl", client.lang.to_string().as_str()),
+            ("tz", client.timezone.param().as_str()),
+            ("geo", client.country.to_string().as_str()),
+            ("ns", "15"),
+        ],
+    )
+    .unwrap();
+
+    if let Some(date) = date {
+        url.query_pairs_mut()
+            .append_pair("ed", &date.format("%Y%m%d").to_string());
+    }
+
+    Ok(vec![url])
+}
+
+// Realtime trends are only available for a specific country
+fn realtime_trends_request<C>(client: &Client<C>, category: &str) -> Result<Vec<Url>> {
+    if client.country == Country::ALL {
+        return Err(Error::CountryRequired);
+    }
+
+    let url = Url::parse_with_params(
+        REALTIME_TRENDS_ENDPOINT,
+        &[
+            ("hl", client.lang.to_string().as_str()),
+            ("tz", client.timezone.param().as_str()),
+            ("cat", category),
+            ("fi", "0"),
+            ("fs", "0"),
+            ("geo", client.country.to_string().as_str()),
+            ("ri", "300"),
+            ("rs", "20"),
+            ("sort", "0"),
+        ],
+    )
+    .unwrap();
+
+    Ok(vec![url])
+}
+
+// The id is a path segment, it is percent-encoded
+fn story_request<C>(client: &Client<C>, id: &str) -> Url {
+    let mut url = Url::parse(STORIES_ENDPOINT).unwrap();
+    url.path_segments_mut().unwrap().pop_if_empty().push(id);
+    url.query_pairs_mut()
+        .append_pair("hl", client.lang.to_string().as_str())
+        .append_pair("tz", &client.timezone.param())
+        .append_pair("id", id);
+    url
+}
+
+// The term is a path segment, it is percent-encoded
+fn autocomplete_request<C>(client: &Client<C>, term: &str) -> Url {
+    let mut url = Url::parse(AUTOCOMPLETE_ENDPOINT).unwrap();
+    url.path_segments_mut().unwrap().pop_if_empty().push(term);
+    url.query_pairs_mut()
+        .append_pair("hl", client.lang.to_string().as_str())
+        .append_pair("tz", &client.timezone.param());
+    url
+}
+
+// Retrieve a widget of the explore response by id, and by keyword when set
+fn widget<'a, C>(client: &'a Client<C>, id: &str, keyword: Option<&str>) -> Result<&'a Widget> {
+    match keyword {
+        Some(keyword) => {
+            let index = client.keywords.iter().position(|item| item.value() == keyword);
+            item_widget(client, id, index, keyword)
         }
+        None => client.response.widget(id).ok_or_else(|| Error::MissingWidget {
+            id: id.to_string(),
+            keyword: None,
+        }),
     }
 }
 
-fn build_query(client: &Client, url: Url, request: String, token: String) -> RequestBuilder {
-    client.client.get(url).query(&[
-        ("hl", client.lang.to_string().as_str()),
-        ("tz", "-120"),
-        ("req", request.as_str()),
-        ("token", token.as_str()),
-        ("tz", "-120"),
-    ])
+// The same keyword can be compared several times, the index of its comparison item picks the widget
+fn item_widget<'a, C>(client: &'a Client<C>, id: &str, index: Option<usize>, keyword: &str) -> Result<&'a Widget> {
+    index
+        .and_then(|index| client.response.widget_at(id, index))
+        .filter(|widget| widget.keyword.as_deref() == Some(keyword))
+        .or_else(|| client.response.widget_for(id, keyword))
+        .ok_or_else(|| Error::MissingWidget {
+            id: id.to_string(),
+            keyword: Some(keyword.to_string()),
+        })
+}
+
+fn build_query<C>(client: &Client<C>, endpoint: &str, request: String, token: &str) -> Url {
+    Url::parse_with_params(
+        endpoint,
+        &[
+            ("hl", client.lang.to_string().as_str()),
+            ("tz", client.timezone.param_for(&client.time).as_str()),
+            ("req", request.as_str()),
+            ("token", token),
+            ("tz", client.timezone.param_for(&client.time).as_str()),
+        ],
+    )
+    .unwrap()
 }
 
-fn mod_region_request(request: Value, resolution: &str) -> Value {
-    let mut config: HashMap<String, Value> =
-        serde_json::from_value(request).expect("unable to parse JSON request");
-    if let Some(mut res) = config["resolution"].as_str() {
-        res = resolution;
-        config.insert("resolution".to_string(), Value::from(res));
+fn mod_region_request(request: &Value, resolution: Resolution, include_low_search_volume: bool) -> Result<Value> {
+    let mut config: HashMap<String, Value> = serde_json::from_value(request.clone())?;
+    if config.get("resolution").is_some_and(Value::is_string) {
+        config.insert("resolution".to_string(), Value::from(resolution.to_string()));
+        config.insert(
+            "includeLowSearchVolumeGeos".to_string(),
+            Value::from(include_low_search_volume),
+        );
     } else {
-        panic!("Unknown resolution");
+        return Err(Error::MissingWidget {
+            id: GEO_MAP.to_string(),
+            keyword: None,
+        });
     }
-    serde_json::to_value(config).unwrap()
+    Ok(serde_json::to_value(config)?)
 }
@@ -0,0 +1,64 @@
+//! Represent the resolution of a Google Trend geo map.
+
+use crate::errors::{Error, Result};
+use crate::Country;
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+/// Create a new Resolution.
+///
+/// The available resolutions are :
+/// - `Country`, only for all the countries (`Country::ALL`)
+/// - `Region`, the subregions of a country (states, provinces, ...)
+/// - `Metro`, the metro areas (DMA) of the United States (`Country::US`)
+/// - `City`
+///
+/// Returns a `Resolution` instance.
+///
+/// # Example
+/// ```
+/// # use rtrend::{Country, Resolution};
+/// let resolution = Resolution::Metro;
+///
+/// assert_eq!(resolution.to_string(), "DMA");
+/// assert!(resolution.check(&Country::US).is_ok());
+/// assert!(resolution.check(&Country::FR).is_err());
+/// assert!(Resolution::Region.check(&Country::ALL).is_err());
+/// ```
+#[derive(PartialEq, Eq, Display, Debug, EnumString, Clone, Copy, EnumVariantNames)]
+pub enum Resolution {
+    #[strum(serialize = "COUNTRY")]
+    Country,
+    #[strum(serialize = "REGION")]
+    Region,
+    #[strum(to_string = "DMA", serialize = "METRO")]
+    Metro,
+    #[strum(serialize = "CITY")]
+    City,
+}
+
+impl Resolution {
+    /// Resolution used by default for a geo, `Country` for all the countries and `Region` otherwise.
+    pub fn default_for(geo: &Country) -> Self {
+        match geo {
+            Country::ALL => Resolution::Country,
+            _ => Resolution::Region,
+        }
+    }
+
+    /// Check the resolution is available for a geo.
+    ///
+    /// Returns an `Error::InvalidResolution` if it isn't.
+    pub fn check(&self, geo: &Country) -> Result<()> {
+        let reason = match (self, geo) {
+            (Resolution::Country, Country::ALL) | (Resolution::Metro, Country::US) | (Resolution::City, _) => {
+                return Ok(())
+            }
+            (Resolution::Region, Country::ALL) => "the regions need a specific country, use `Resolution::Country`",
+            (Resolution::Region, _) => return Ok(()),
+            (Resolution::Metro, _) => "the metro areas (DMA) are only available for `Country::US`",
+            (Resolution::Country, _) => "a single country can't be split by country, use `Resolution::Region`",
+        };
+
+        Err(Error::InvalidResolution(format!("{} for {:?}: {}", self, geo, reason)))
+    }
+}
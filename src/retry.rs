@@ -0,0 +1,266 @@
+//! Retry the requests Google Trend rejects and pace the ones sent.
+//!
+//! Google Trend answers `429 Too Many Requests` as soon as a client sends too many requests.
+//! A [`RetryPolicy`] retries them after an exponential backoff, or after the delay asked by the `Retry-After` header,
+//! and a [`RateLimiter`] spreads the requests of a client and its clones so fewer of them are rejected.
+
+use crate::errors::{Error, Result};
+use crate::transport::HttpResponse;
+use crate::utils;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Create a new RetryPolicy.
+///
+/// Returns a RetryPolicy instance.
+///
+/// Requests answered with `429 Too Many Requests` or a server error, and requests which timed out, are sent again
+/// until `max_attempts` requests have been sent, by the blocking and the asynchronous clients.
+/// The delay before the n-th retry is `base_delay * 2^(n - 1)`, capped at `max_delay`, half of it being random.
+/// The delay asked by a `Retry-After` header is honored instead, capped at `max_delay` too.
+/// After `refresh_cookie_after` consecutive `429`, the consent cookie is retrieved again before the next retry.
+///
+/// # Example
+/// ```
+/// # use rtrend::RetryPolicy;
+/// # use std::time::Duration;
+/// let policy = RetryPolicy::new(5)
+///     .with_base_delay(Duration::from_secs(2))
+///     .with_max_delay(Duration::from_secs(30));
+///
+/// assert!(policy.delay(1) >= Duration::from_secs(1) && policy.delay(1) <= Duration::from_secs(2));
+/// assert!(policy.delay(10) <= Duration::from_secs(30));
+/// assert_eq!(RetryPolicy::none().max_attempts, 1);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub refresh_cookie_after: u32,
+}
+
+/// By default, a request is sent at most 3 times, waiting 1 second then 2 seconds,
+/// and the cookie is refreshed after 2 consecutive `429`.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            refresh_cookie_after: 2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request at most `max_attempts` times, at least once.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Send every request only once.
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Set the delay before the first retry.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the longest delay between two attempts, `Retry-After` included.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Retrieve a new consent cookie after `refresh_cookie_after` consecutive `429`, `0` never refreshes it.
+    pub fn with_refresh_cookie_after(mut self, refresh_cookie_after: u32) -> Self {
+        self.refresh_cookie_after = refresh_cookie_after;
+        self
+    }
+
+    /// Delay before the `retry`-th retry, starting at 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        let backoff = self
+            .base_delay
+            .checked_mul(1 << exponent)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        let half = backoff / 2;
+        half + half.mul_f64(random_ratio())
+    }
+
+    pub(crate) fn should_refresh_cookie(&self, rate_limited: u32) -> bool {
+        // `0` never refreshes the cookie
+        rate_limited.checked_rem(self.refresh_cookie_after) == Some(0)
+    }
+
+    // Retry the `429`, the server errors and the requests which timed out until the last attempt
+    pub(crate) fn outcome(&self, attempt: u32, result: Result<HttpResponse>) -> Outcome {
+        let last = attempt >= self.max_attempts;
+
+        match result {
+            Ok(resp) if resp.status == StatusCode::TOO_MANY_REQUESTS && last => Outcome::Done(Err(Error::RateLimited {
+                status: resp.status,
+                excerpt: utils::excerpt(&resp.body),
+            })),
+            Ok(resp) if resp.status == StatusCode::TOO_MANY_REQUESTS || (resp.status.is_server_error() && !last) => {
+                Outcome::Retry {
+                    retry_after: retry_after(&resp.headers).map(|delay| delay.min(self.max_delay)),
+                    rate_limited: resp.status == StatusCode::TOO_MANY_REQUESTS,
+                }
+            }
+            Ok(resp) => {
+                trace_event!(debug, status = resp.status.as_u16(), attempt, "response received");
+                Outcome::Done(utils::check_response(resp.status, &resp.body).map(|_| resp))
+            }
+            Err(Error::Transport(error)) if (error.is_timeout() || error.is_connect()) && !last => {
+                trace_event!(warn, error = %error, attempt, "request failed");
+                Outcome::Retry {
+                    retry_after: None,
+                    rate_limited: false,
+                }
+            }
+            Err(error) => Outcome::Done(Err(error)),
+        }
+    }
+}
+
+// What to do once an attempt has been answered, shared by the blocking and the asynchronous clients
+pub(crate) enum Outcome {
+    // The response or the error to return
+    Done(Result<HttpResponse>),
+    // Send the request again, after the delay asked by Google if any
+    Retry {
+        retry_after: Option<Duration>,
+        rate_limited: bool,
+    },
+}
+
+// Delay asked by a `Retry-After` header, in seconds or as an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = DateTime::parse_from_rfc2822(value).ok()?;
+            Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+        }
+    }
+}
+
+// Random number in [0, 1), the jitter doesn't need a better generator than the one seeding the hash maps
+fn random_ratio() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Create a new RateLimiter.
+///
+/// Returns a RateLimiter instance.
+///
+/// The limiter is a token bucket: it allows bursts of `requests` requests, then one request every `per / requests`.
+/// Clones share the same bucket, so a client and all its clones are limited together.
+/// The asynchronous client waits for its tokens without blocking its thread.
+///
+/// # Example
+/// ```
+/// # use rtrend::RateLimiter;
+/// # use std::time::Duration;
+/// let limiter = RateLimiter::new(2, Duration::from_secs(3600));
+/// let clone = limiter.clone();
+///
+/// limiter.acquire();
+/// assert!(clone.try_acquire());
+///
+/// // The bucket is shared and empty, the third request would wait for a token
+/// assert!(!limiter.try_acquire());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    // Tokens added per second
+    rate: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests` requests every `per`, at least one.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        let capacity = f64::from(requests.max(1));
+        let rate = capacity / per.as_secs_f64().max(f64::EPSILON);
+
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                tokens: capacity,
+                rate,
+                refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// Take a token, waiting for one if the bucket is empty.
+    pub fn acquire(&self) {
+        while let Some(wait) = self.take() {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Take a token if one is left, without waiting.
+    ///
+    /// Returns whether a token was taken.
+    pub fn try_acquire(&self) -> bool {
+        self.take().is_none()
+    }
+
+    // Asynchronous counterpart of `acquire`, the task waits without blocking the thread
+    #[cfg(feature = "async")]
+    pub(crate) async fn acquire_async(&self) {
+        while let Some(wait) = self.take() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    // Take a token, or tell how long to wait for the next one
+    fn take(&self) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill();
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return None;
+        }
+        Some(Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.rate))
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.refilled_at = now;
+    }
+}
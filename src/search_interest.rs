@@ -4,30 +4,112 @@
 //! A value of 100 is the peak popularity for the term. A value of 50 means that the term is half as popular.
 //! A score of 0 means there was not enough data for this term.
 
-use crate::Client;
+use crate::errors::Result;
+use crate::transport::Transport;
 use crate::request_handler::Query;
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::stitch::{self, StitchedSeries};
+use crate::{utils, Client, TimeRange, Timezone};
 
-use serde_json::Value;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+
+// Correpond to Multiline request => Google trend interest curve
+#[derive(Clone, Debug, Deserialize)]
+pub struct MultilineResponse {
+	default: SearchInterestResponse
+}
+
+/// Interest over time of the keywords set within the client.
+///
+/// # Example
+/// ```
+/// # use rtrend::search_interest::SearchInterestResponse;
+/// let body = r#"{
+///     "timelineData": [{
+///         "time": "1609459200",
+///         "formattedTime": "Jan 1, 2021",
+///         "formattedAxisTime": "Jan 1",
+///         "value": [42, 100],
+///         "hasData": [true, true],
+///         "formattedValue": ["42", "100"],
+///         "isPartial": true
+///     }],
+///     "averages": [42, 100]
+/// }"#;
+///
+/// let response: SearchInterestResponse = serde_json::from_str(body).unwrap();
+/// let point = &response.timeline_data[0];
+///
+/// assert_eq!(point.time.timestamp(), 1609459200);
+/// assert_eq!(point.value, vec![42, 100]);
+/// assert!(point.is_partial);
+/// assert_eq!(response.averages, vec![42, 100]);
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchInterestResponse {
+	pub timeline_data: Vec<TimelinePoint>,
+	/// Average interest of each keyword over the period, empty when Google does not send it.
+	#[serde(default)]
+	pub averages: Vec<u8>
+}
+
+impl SearchInterestResponse {
+    // Google sends unix timestamps, give every point the offset sent with the request
+    pub(crate) fn localize(mut self, timezone: &Timezone, period: &str) -> Self {
+        let offset = timezone.offset_for(period);
+        for point in self.timeline_data.iter_mut() {
+            point.time = point.time.with_timezone(&offset);
+        }
+        self
+    }
+}
+
+/// A point of the interest curve, `value`, `has_data` and `formatted_value` hold one entry per keyword, in the order of the `Keywords`.
+///
+/// `time` carries the offset sent to Google for the period, see [`Timezone::offset_for`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelinePoint {
+	#[serde(with = "utils::timestamp")]
+	pub time: DateTime<FixedOffset>,
+	pub formatted_time: CompactString,
+	#[serde(default)]
+	pub formatted_axis_time: CompactString,
+	pub value: Vec<u8>,
+	pub has_data: Vec<bool>,
+	pub formatted_value: Vec<CompactString>,
+	/// The point covers a period which is not over yet.
+	#[serde(default)]
+	pub is_partial: bool
+}
 
 #[derive(Clone, Debug, Default)]
-pub struct SearchInterest {
-    pub client: Client,
+pub struct SearchInterest<C = Client> {
+    pub client: C,
 }
 
-impl SearchInterest {
+impl<C> SearchInterest<Client<C>> {
     /// Create a `SearchInterest` instance.
     /// 
     /// Returns a `SearchInterest` instance
-    pub fn new(client: Client) -> Self {
+    pub fn new(client: Client<C>) -> Self {
         Self { client }
     }
+}
 
+impl<T: Transport> SearchInterest<Client<T>> {
     /// Retrieve line chart data (Timeseries data) for all keywords.
     ///
     /// Retrieve data for all keywords set within the client.
     ///
-    /// Returns a JSON serde Value (`serde_json::Value`).
-    /// ```
+    /// Returns a `SearchInterestResponse`.
+    /// ```no_run
     /// # use rtrend::{Country, Keywords, Client, SearchInterest};
     /// let keywords = Keywords::new(vec!["Candy"]);
     /// let country = Country::US;
@@ -36,9 +118,218 @@ impl SearchInterest {
     /// 
     /// let search_interest = SearchInterest::new(client).get();
     /// 
-    /// println!("{}", search_interest);
+    /// for point in search_interest.timeline_data {
+    ///     println!("{} {:?}", point.time, point.value);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the client have not been built or if the request fails, see [`SearchInterest::try_get`].
+    pub fn get(&self) -> SearchInterestResponse {
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve line chart data (Timeseries data) for all keywords without panicking.
+    ///
+    /// Returns a `SearchInterestResponse` or an `Error` if the request fails.
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, SearchInterest};
+    /// # fn main() -> Result<(), rtrend::Error> {
+    /// let keywords = Keywords::new(vec!["Candy"]);
+    /// let client = Client::try_new(keywords, Country::US)?.try_build()?;
+    ///
+    /// let search_interest = SearchInterest::new(client).try_get()?;
+    ///
+    /// println!("{:#?}", search_interest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_get(&self) -> Result<SearchInterestResponse> {
+        let response = self.send_request(None)?.remove(0).default;
+        Ok(response.localize(&self.client.timezone, &self.client.time))
+    }
+
+    /// Retrieve a daily curve from `start` to `end` (both included), whatever the length of the period.
+    ///
+    /// Google Trend only sends daily points for periods up to ~270 days.
+    /// Longer periods are split into overlapping windows, each window is requested with its own explore request,
+    /// then the curves are stitched into one series normalized to 0–100, see [`StitchedSeries::stitch`].
+    ///
+    /// The period of the client and of the comparison items is ignored.
+    ///
+    /// Returns a `StitchedSeries`.
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, SearchInterest};
+    /// # use chrono::NaiveDate;
+    /// let keywords = Keywords::new(vec!["Candy"]);
+    /// let client = Client::new(keywords, Country::US);
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2019, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2021, 12, 31).unwrap();
+    /// let series = SearchInterest::new(client).daily_over(start, end);
+    ///
+    /// for point in series.points {
+    ///     println!("{} {:?}", point.time, point.value);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the period is invalid, if a request fails or if a window can't be rescaled, see [`SearchInterest::try_daily_over`].
+    pub fn daily_over(&self, start: NaiveDate, end: NaiveDate) -> StitchedSeries {
+        self.try_daily_over(start, end)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve a daily curve from `start` to `end` (both included) without panicking.
+    ///
+    /// Returns a `StitchedSeries` or an `Error` if the period is invalid or if a request fails,
+    /// or an `Error::WindowWithoutOverlap` if a window has interest but its 30 shared days have none, see [`StitchedSeries::try_stitch`].
+    pub fn try_daily_over(&self, start: NaiveDate, end: NaiveDate) -> Result<StitchedSeries> {
+        TimeRange::Dates(start, end).check()?;
+
+        let mut chunks = Vec::new();
+        for (from, to) in stitch::day_windows(start, end) {
+            let client = self
+                .client
+                .clone()
+                .with_window(TimeRange::Dates(from, to))
+                .try_build()?;
+            chunks.push(SearchInterest::new(client).try_get()?);
+        }
+        StitchedSeries::try_stitch(&chunks)
+    }
+
+    /// Retrieve an hourly curve from `start` to `end` (UTC, both included), whatever the length of the period.
+    ///
+    /// Google Trend only sends hourly points for periods up to a week.
+    /// Longer periods are walked in 7 days windows overlapping by a day, each window is requested with its own explore request,
+    /// then the curves are aligned on their shared hours and stitched into one series normalized to 0–100.
+    /// The `chunks` of the series tell which window each point comes from.
+    ///
+    /// The period of the client and of the comparison items is ignored.
+    ///
+    /// Returns a `StitchedSeries`.
+    /// ```no_run
+    /// # use rtrend::{Country, Keywords, Client, SearchInterest};
+    /// # use chrono::NaiveDate;
+    /// let keywords = Keywords::new(vec!["Candy"]);
+    /// let client = Client::new(keywords, Country::US);
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap().and_hms_opt(23, 0, 0).unwrap();
+    /// let series = SearchInterest::new(client).hourly_over(start, end);
+    ///
+    /// for point in series.points {
+    ///     println!("{} {:?} (window {})", point.time, point.value, point.chunk);
+    /// }
     /// ```
-    pub fn get(&self) -> Value {
-        self.send_request()[0].clone()
+    ///
+    /// # Panics
+    /// Panic if the period is invalid, if a request fails or if a window can't be rescaled, see [`SearchInterest::try_hourly_over`].
+    pub fn hourly_over(&self, start: NaiveDateTime, end: NaiveDateTime) -> StitchedSeries {
+        self.try_hourly_over(start, end)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve an hourly curve from `start` to `end` (UTC, both included) without panicking.
+    ///
+    /// Returns a `StitchedSeries` or an `Error` if the period is invalid (empty, not on the hour) or if a request fails,
+    /// or an `Error::WindowWithoutOverlap` if a window has interest but its 24 shared hours have none, see [`StitchedSeries::try_stitch`].
+    pub fn try_hourly_over(&self, start: NaiveDateTime, end: NaiveDateTime) -> Result<StitchedSeries> {
+        let windows = stitch::hour_windows(start, end);
+        for &(from, to) in &windows {
+            TimeRange::Hours(from, to).check()?;
+        }
+
+        let mut chunks = Vec::new();
+        for (from, to) in windows {
+            let client = self
+                .client
+                .clone()
+                .with_window(TimeRange::Hours(from, to))
+                .try_build()?;
+            chunks.push(SearchInterest::new(client).try_get()?);
+        }
+        StitchedSeries::try_stitch(&chunks)
+    }
+}
+
+#[cfg(feature = "async")]
+impl SearchInterest<AsyncClient> {
+    /// Asynchronously retrieve line chart data (Timeseries data) for all keywords.
+    ///
+    /// # Panics
+    /// Panic if the client have not been built or if the request fails.
+    pub async fn get(&self) -> SearchInterestResponse {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve line chart data (Timeseries data) for all keywords without panicking.
+    pub async fn try_get(&self) -> Result<SearchInterestResponse> {
+        let response = self.send_request(None).await?.remove(0).default;
+        Ok(response.localize(&self.client.timezone, &self.client.time))
+    }
+
+    /// Asynchronously retrieve a daily curve from `start` to `end` (both included), see [`SearchInterest::daily_over`].
+    ///
+    /// # Panics
+    /// Panic if the period is invalid, if a request fails or if a window can't be rescaled.
+    pub async fn daily_over(&self, start: NaiveDate, end: NaiveDate) -> StitchedSeries {
+        self.try_daily_over(start, end)
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve a daily curve from `start` to `end` (both included) without panicking.
+    pub async fn try_daily_over(&self, start: NaiveDate, end: NaiveDate) -> Result<StitchedSeries> {
+        TimeRange::Dates(start, end).check()?;
+
+        let mut chunks = Vec::new();
+        for (from, to) in stitch::day_windows(start, end) {
+            let client = self
+                .client
+                .clone()
+                .with_window(TimeRange::Dates(from, to))
+                .try_build()
+                .await?;
+            chunks.push(SearchInterest::new(client).try_get().await?);
+        }
+        StitchedSeries::try_stitch(&chunks)
+    }
+
+    /// Asynchronously retrieve an hourly curve from `start` to `end` (UTC, both included), see [`SearchInterest::hourly_over`].
+    ///
+    /// # Panics
+    /// Panic if the period is invalid, if a request fails or if a window can't be rescaled.
+    pub async fn hourly_over(&self, start: NaiveDateTime, end: NaiveDateTime) -> StitchedSeries {
+        self.try_hourly_over(start, end)
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve an hourly curve from `start` to `end` (UTC, both included) without panicking.
+    pub async fn try_hourly_over(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<StitchedSeries> {
+        let windows = stitch::hour_windows(start, end);
+        for &(from, to) in &windows {
+            TimeRange::Hours(from, to).check()?;
+        }
+
+        let mut chunks = Vec::new();
+        for (from, to) in windows {
+            let client = self
+                .client
+                .clone()
+                .with_window(TimeRange::Hours(from, to))
+                .try_build()
+                .await?;
+            chunks.push(SearchInterest::new(client).try_get().await?);
+        }
+        StitchedSeries::try_stitch(&chunks)
     }
 }
@@ -0,0 +1,403 @@
+//! Keep the cookies of the consent handshake between the requests, and between the runs.
+
+use crate::errors::{Error, Result};
+use crate::transport::Transport;
+use crate::Cookie;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, SET_COOKIE};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+const HANDSHAKE: &str = "https://consent.google.com/s?continue=https://www.google.com/";
+const TRENDS: &str = "https://trends.google.com/trends/";
+const DEFAULT_DOMAIN: &str = "google.com";
+
+// Whether a request is the consent handshake itself, it is sent without session
+pub(crate) fn is_handshake(url: &Url) -> bool {
+    let handshake = Url::parse(HANDSHAKE).expect("the handshake url is valid");
+    url.host_str() == handshake.host_str() && url.path() == handshake.path()
+}
+
+/// A cookie set by the consent handshake.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCookie {
+    pub name: String,
+    pub value: String,
+    /// Domain the cookie is sent to, `google.com` when not set.
+    pub domain: Option<String>,
+    /// `None` for a cookie without expiry.
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl SessionCookie {
+    /// Parse a `Set-Cookie` header, `Max-Age` taking precedence over `Expires`.
+    ///
+    /// Returns `None` if the header has no `name=value` pair.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::SessionCookie;
+    /// # use chrono::{TimeZone, Utc};
+    /// let now = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    /// let cookie = SessionCookie::parse(
+    ///     "NID=511=abc; expires=Sat, 03-Jul-2021 00:00:00 GMT; path=/; domain=.google.com; HttpOnly",
+    ///     now,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(cookie.name, "NID");
+    /// assert_eq!(cookie.value, "511=abc");
+    /// assert_eq!(cookie.domain.as_deref(), Some("google.com"));
+    /// assert_eq!(cookie.expires, Some(Utc.with_ymd_and_hms(2021, 7, 3, 0, 0, 0).unwrap()));
+    /// ```
+    pub fn parse(set_cookie: &str, now: DateTime<Utc>) -> Option<Self> {
+        let mut parts = set_cookie.split(';').map(str::trim);
+        let (name, value) = parts.next()?.split_once('=')?;
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Self {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: None,
+            expires: None,
+        };
+        let mut max_age = None;
+
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            match key.to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    cookie.domain = Some(value.trim_start_matches('.').to_string())
+                }
+                "expires" => cookie.expires = parse_http_date(value),
+                "max-age" => max_age = value.parse::<i64>().ok().map(|seconds| now + Duration::seconds(seconds)),
+                _ => (),
+            }
+        }
+        cookie.expires = max_age.or(cookie.expires);
+        Some(cookie)
+    }
+
+    /// Whether the cookie has expired at `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn to_set_cookie(&self) -> String {
+        let domain = self.domain.as_deref().unwrap_or(DEFAULT_DOMAIN);
+        let mut set_cookie = format!("{}={}; Domain={}; Path=/", self.name, self.value, domain);
+        if let Some(expires) = self.expires {
+            set_cookie.push_str(&expires.format("; Expires=%a, %d %b %Y %H:%M:%S GMT").to_string());
+        }
+        set_cookie
+    }
+}
+
+// Google sends `Sat, 03-Jul-2021 00:00:00 GMT`, the RFC sends `Sat, 03 Jul 2021 00:00:00 GMT`
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    ["%a, %d-%b-%Y %H:%M:%S GMT", "%a, %d %b %Y %H:%M:%S GMT"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|time| time.and_utc())
+}
+
+/// Create a new Session.
+///
+/// Returns a Session instance.
+///
+/// A session holds every cookie of the consent handshake (`NID`, `CONSENT`, ...) in a reqwest cookie jar,
+/// which sends them with the requests to Google Trend as long as they have not expired.
+/// A client refreshes its session when a cookie has expired or when Google keeps rejecting it,
+/// see [`RetryPolicy`](crate::RetryPolicy).
+/// A session loaded from or saved to a file is saved again after every refresh, so short runs can share it.
+/// Clones share the same cookies.
+///
+/// # Example
+/// ```
+/// # use rtrend::{ClientBuilder, Keywords, Session};
+/// # use rtrend::transport::{Fixtures, HttpResponse};
+/// # use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+/// let mut headers = HeaderMap::new();
+/// headers.append(SET_COOKIE, HeaderValue::from_static("NID=511=fresh; Max-Age=15552000; Domain=.google.com"));
+/// headers.append(SET_COOKIE, HeaderValue::from_static("CONSENT=PENDING+123; Max-Age=15552000; Domain=.google.com"));
+/// let fixtures = Fixtures::new()
+///     .with_response("/s", HttpResponse { headers, ..HttpResponse::ok("") })
+///     .with("explore", r#")]}'{"widgets": []}"#);
+///
+/// // The cookie of the last run has expired
+/// let path = std::env::temp_dir().join("rtrend-session.json");
+/// Session::from_set_cookies(&["NID=511=stale; Expires=Fri, 01 Jan 2021 00:00:00 GMT"]).try_save(&path).unwrap();
+///
+/// let session = Session::try_load(&path).unwrap();
+/// assert!(session.is_expired());
+///
+/// let client = ClientBuilder::new()
+///     .with_keywords(Keywords::new(vec!["rust"]))
+///     .with_session(session)
+///     .build_with(fixtures)
+///     .unwrap()
+///     .try_build()
+///     .unwrap();
+///
+/// // Refreshed before the explore request, and saved for the next run
+/// assert_eq!(client.session.get("NID").unwrap().value, "511=fresh");
+/// let header = client.session.header().unwrap();
+/// assert!(header.to_str().unwrap().contains("CONSENT=PENDING+123"));
+/// assert!(!Session::try_load(&path).unwrap().is_expired());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    state: Arc<RwLock<SessionState>>,
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    cookies: Vec<SessionCookie>,
+    jar: Jar,
+    path: Option<PathBuf>,
+}
+
+impl SessionState {
+    fn new(cookies: Vec<SessionCookie>, path: Option<PathBuf>) -> Self {
+        let jar = Jar::default();
+        let handshake = Url::parse(HANDSHAKE).expect("the handshake url is valid");
+        for cookie in &cookies {
+            jar.add_cookie_str(&cookie.to_set_cookie(), &handshake);
+        }
+
+        Self { cookies, jar, path }
+    }
+}
+
+impl Session {
+    /// Create a session holding these cookies.
+    pub fn new(cookies: Vec<SessionCookie>) -> Self {
+        Self::with_state(SessionState::new(cookies, None))
+    }
+
+    /// Create a session from `Set-Cookie` headers, the invalid ones are ignored.
+    pub fn from_set_cookies<S: AsRef<str>>(set_cookies: &[S]) -> Self {
+        let now = Utc::now();
+        Self::new(
+            set_cookies
+                .iter()
+                .filter_map(|set_cookie| SessionCookie::parse(set_cookie.as_ref(), now))
+                .collect(),
+        )
+    }
+
+    fn with_state(state: SessionState) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(state)),
+        }
+    }
+
+    /// Retrieve a new session with the consent handshake, sent through `transport`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the handshake fails, see [`Session::try_fetch`].
+    pub fn fetch<T: Transport>(transport: &T) -> Self {
+        Self::try_fetch(transport).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve a new session with the consent handshake, sent through `transport`.
+    ///
+    /// The cookies the handshake deletes, already expired, are left out.
+    ///
+    /// Returns an error if the request fails or if the handshake doesn't set the `NID` cookie.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::Session;
+    /// # use rtrend::transport::{Fixtures, HttpResponse};
+    /// # use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+    /// let mut headers = HeaderMap::new();
+    /// headers.append(SET_COOKIE, HeaderValue::from_static("NID=511=fresh; Max-Age=15552000; Domain=.google.com"));
+    /// headers.append(SET_COOKIE, HeaderValue::from_static("CONSENT=; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Domain=.google.com"));
+    /// let fixtures = Fixtures::new().with_response("/s", HttpResponse { headers, ..HttpResponse::ok("") });
+    ///
+    /// let session = Session::try_fetch(&fixtures).unwrap();
+    ///
+    /// assert!(!session.is_expired());
+    /// assert!(session.get("CONSENT").is_none());
+    /// ```
+    pub fn try_fetch<T: Transport>(transport: &T) -> Result<Self> {
+        Ok(Self::new(Self::handshake(transport)?))
+    }
+
+    /// Asynchronous version of [`Session::try_fetch`], available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub(crate) async fn try_fetch_async(client: &reqwest::Client) -> Result<Self> {
+        let response = client.get(HANDSHAKE).send().await?;
+        Ok(Self::new(Self::parse_handshake(response.headers())?))
+    }
+
+    fn handshake<T: Transport>(transport: &T) -> Result<Vec<SessionCookie>> {
+        let url = Url::parse(HANDSHAKE).expect("the handshake url is valid");
+        let response = transport.get(&url, &HeaderMap::new())?;
+        Self::parse_handshake(&response.headers)
+    }
+
+    fn parse_handshake(headers: &HeaderMap) -> Result<Vec<SessionCookie>> {
+        let now = Utc::now();
+        let cookies: Vec<SessionCookie> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| SessionCookie::parse(value, now))
+            .filter(|cookie| !cookie.is_expired_at(now))
+            .collect();
+
+        if !cookies.iter().any(|cookie| cookie.name == "NID") {
+            return Err(Error::Cookie("no NID cookie set by the handshake".to_string()));
+        }
+        Ok(cookies)
+    }
+
+    /// Replace the cookies with new ones from the consent handshake, for the session and all its clones.
+    ///
+    /// The session is saved again if it has been loaded from or saved to a file.
+    ///
+    /// Returns an error if the handshake fails or if the file can't be written, the cookies are then left untouched.
+    pub fn try_refresh<T: Transport>(&self, transport: &T) -> Result<()> {
+        self.install(Self::handshake(transport)?)
+    }
+
+    // Asynchronous counterpart of `try_refresh`, used by the asynchronous client
+    #[cfg(feature = "async")]
+    pub(crate) async fn try_refresh_async(&self, client: &reqwest::Client) -> Result<()> {
+        let response = client.get(HANDSHAKE).send().await?;
+        self.install(Self::parse_handshake(response.headers())?)
+    }
+
+    // Replace the cookies, once saved if the session has a file
+    fn install(&self, cookies: Vec<SessionCookie>) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        if let Some(path) = &state.path {
+            save(&cookies, path)?;
+        }
+
+        let path = state.path.take();
+        *state = SessionState::new(cookies, path);
+        Ok(())
+    }
+
+    /// Cookies of the session.
+    pub fn cookies(&self) -> Vec<SessionCookie> {
+        self.state.read().unwrap().cookies.clone()
+    }
+
+    /// Cookie of the session with this name.
+    pub fn get(&self, name: &str) -> Option<SessionCookie> {
+        self.cookies().into_iter().find(|cookie| cookie.name == name)
+    }
+
+    /// Whether a cookie of the session has expired, an empty session never expires.
+    pub fn is_expired(&self) -> bool {
+        let now = Utc::now();
+        self.state
+            .read()
+            .unwrap()
+            .cookies
+            .iter()
+            .any(|cookie| cookie.is_expired_at(now))
+    }
+
+    /// `Cookie` header sent to Google Trend, `None` if no cookie is left.
+    pub fn header(&self) -> Option<HeaderValue> {
+        let trends = Url::parse(TRENDS).expect("the trends url is valid");
+        self.state.read().unwrap().jar.cookies(&trends)
+    }
+
+    pub fn add_to_header(&self, mut header: HeaderMap) -> HeaderMap {
+        if let Some(value) = self.header() {
+            header.insert(COOKIE, value);
+        }
+        header
+    }
+
+    /// Save the session to a JSON file, it is then saved again after every refresh.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the file can't be written, see [`Session::try_save`].
+    pub fn save(&self, path: impl AsRef<Path>) {
+        self.try_save(path).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Save the session to a JSON file, it is then saved again after every refresh.
+    ///
+    /// Returns an `Error::Cookie` if the file can't be written.
+    pub fn try_save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        save(&state.cookies, path.as_ref())?;
+        state.path = Some(path.as_ref().to_path_buf());
+        Ok(())
+    }
+
+    /// Load a session saved with [`Session::save`], it is then saved again after every refresh.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the file can't be read, see [`Session::try_load`].
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        Self::try_load(path).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Load a session saved with [`Session::save`], it is then saved again after every refresh.
+    ///
+    /// Returns an `Error::Cookie` if the file can't be read, or an `Error::MalformedBody` if it can't be parsed.
+    pub fn try_load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|error| Error::Cookie(format!("can't read {}: {}", path.display(), error)))?;
+        let cookies = serde_json::from_str(&content)?;
+
+        Ok(Self::with_state(SessionState::new(cookies, Some(path.to_path_buf()))))
+    }
+
+    // Reuse the session saved by a previous run, unless it has expired
+    pub(crate) fn try_load_or_fetch<T: Transport>(path: &Path, transport: &T) -> Result<Self> {
+        match Self::try_load(path) {
+            Ok(session) if !session.is_expired() && session.get("NID").is_some() => Ok(session),
+            _ => {
+                let session = Self::try_fetch(transport)?;
+                session.try_save(path)?;
+                Ok(session)
+            }
+        }
+    }
+
+    // Asynchronous counterpart of `try_load_or_fetch`
+    #[cfg(feature = "async")]
+    pub(crate) async fn try_load_or_fetch_async(path: &Path, client: &reqwest::Client) -> Result<Self> {
+        match Self::try_load(path) {
+            Ok(session) if !session.is_expired() && session.get("NID").is_some() => Ok(session),
+            _ => {
+                let session = Self::try_fetch_async(client).await?;
+                session.try_save(path)?;
+                Ok(session)
+            }
+        }
+    }
+}
+
+fn save(cookies: &[SessionCookie], path: &Path) -> Result<()> {
+    let content = serde_json::to_string_pretty(cookies)?;
+    fs::write(path, content)
+        .map_err(|error| Error::Cookie(format!("can't write {}: {}", path.display(), error)))
+}
+
+/// The `nid` of a cookie is parsed as a `Set-Cookie` header, an empty one gives an empty session.
+impl From<Cookie> for Session {
+    fn from(cookie: Cookie) -> Self {
+        Self::from_set_cookies(&[cookie.nid])
+    }
+}
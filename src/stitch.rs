@@ -0,0 +1,247 @@
+//! Stitch several interest curves into one continuous series.
+//!
+//! Google Trend lowers the resolution of the curve when the period gets long (weekly points after ~270 days,
+//! daily points after a week). To keep a daily or hourly resolution, the period is split into overlapping windows
+//! which are requested one by one.
+//! Each window is normalized on its own peak, so every window is rescaled to the previous ones using their overlap,
+//! then the whole series is normalized back to 0–100.
+
+use crate::errors::{Error, Result};
+use crate::search_interest::SearchInterestResponse;
+use crate::utils;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::{Add, Sub};
+
+// Longest window Google Trend still answers with daily points
+const DAILY_WINDOW_DAYS: i64 = 269;
+// Days shared by two consecutive windows, used to rescale them
+const DAILY_OVERLAP_DAYS: i64 = 30;
+// Longest window Google Trend still answers with hourly points
+const HOURLY_WINDOW_HOURS: i64 = 7 * 24;
+// Hours shared by two consecutive windows, used to rescale them
+const HOURLY_OVERLAP_HOURS: i64 = 24;
+
+/// Interest over time stitched from several requests.
+///
+/// Values are rescaled so the highest point of the whole series is 100.
+///
+/// # Example
+/// ```
+/// # use rtrend::search_interest::SearchInterestResponse;
+/// # use rtrend::stitch::StitchedSeries;
+/// let first: SearchInterestResponse = serde_json::from_str(r#"{"timelineData": [
+///     {"time": "1609459200", "formattedTime": "Jan 1, 2021", "value": [100], "hasData": [true], "formattedValue": ["100"]},
+///     {"time": "1609545600", "formattedTime": "Jan 2, 2021", "value": [50], "hasData": [true], "formattedValue": ["50"]}
+/// ]}"#).unwrap();
+///
+/// // Jan 2 is shared, the second window is normalized on a peak twice as high
+/// let second: SearchInterestResponse = serde_json::from_str(r#"{"timelineData": [
+///     {"time": "1609545600", "formattedTime": "Jan 2, 2021", "value": [25], "hasData": [true], "formattedValue": ["25"]},
+///     {"time": "1609632000", "formattedTime": "Jan 3, 2021", "value": [100], "hasData": [true], "formattedValue": ["100"]}
+/// ]}"#).unwrap();
+///
+/// let series = StitchedSeries::stitch(&[first, second]);
+/// let values: Vec<f64> = series.points.iter().map(|point| point.value[0]).collect();
+///
+/// assert_eq!(values, vec![50.0, 25.0, 100.0]);
+///
+/// // Jan 3 comes from the second window, rescaled by 2
+/// assert_eq!(series.points[2].chunk, 1);
+/// assert_eq!(series.chunks[1].scale, 2.0);
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StitchedSeries {
+	pub points: Vec<StitchedPoint>,
+	/// Windows the series is made of, in the order they were stitched.
+	pub chunks: Vec<Chunk>,
+}
+
+/// A point of a stitched curve, `value` and `has_data` hold one entry per keyword, in the order of the `Keywords`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StitchedPoint {
+	#[serde(with = "utils::timestamp")]
+	pub time: DateTime<FixedOffset>,
+	pub value: Vec<f64>,
+	pub has_data: Vec<bool>,
+	/// The point covers a period which is not over yet.
+	pub is_partial: bool,
+	/// Index of the chunk the point comes from.
+	pub chunk: usize,
+}
+
+/// Provenance of the points of a stitched curve.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chunk {
+	/// First point of the window, `None` when Google sent no point.
+	pub start: Option<DateTime<FixedOffset>>,
+	/// Last point of the window, `None` when Google sent no point.
+	pub end: Option<DateTime<FixedOffset>>,
+	/// Factor applied to the window before the final normalization.
+	pub scale: f64,
+	/// Points shared with the previous windows, used to compute `scale`.
+	pub overlap: usize,
+	/// `false` when the window has interest but shares none with the previous windows:
+	/// its `scale` is then 1 and its values can't be compared with the previous ones.
+	pub rescaled: bool,
+}
+
+impl StitchedSeries {
+    /// Stitch consecutive curves sharing some points.
+    ///
+    /// Each curve is rescaled by the ratio of the sums of the points it shares with the previous ones.
+    /// A curve with interest sharing no interest with the previous ones can't be rescaled:
+    /// it is kept as is and its chunk is flagged with `rescaled: false`, see [`StitchedSeries::try_stitch`] to reject it.
+    ///
+    /// Returns a `StitchedSeries` normalized to 0–100.
+    pub fn stitch(chunks: &[SearchInterestResponse]) -> Self {
+        let mut points: Vec<StitchedPoint> = Vec::new();
+        let mut provenance: Vec<Chunk> = Vec::new();
+        let mut index: HashMap<DateTime<FixedOffset>, usize> = HashMap::new();
+
+        for (n, chunk) in chunks.iter().enumerate() {
+            let (mut known, mut fetched, mut overlap) = (0.0, 0.0, 0);
+            for point in &chunk.timeline_data {
+                if let Some(&i) = index.get(&point.time) {
+                    known += points[i].value.iter().sum::<f64>();
+                    fetched += point.value.iter().map(|&v| f64::from(v)).sum::<f64>();
+                    overlap += 1;
+                }
+            }
+            let matched = known > 0.0 && fetched > 0.0;
+            let scale = if matched { known / fetched } else { 1.0 };
+            // Only the first window and the windows without interest don't need a reference
+            let rescaled = matched
+                || n == 0
+                || chunk
+                    .timeline_data
+                    .iter()
+                    .all(|point| point.value.iter().all(|&v| v == 0));
+
+            for point in &chunk.timeline_data {
+                if index.contains_key(&point.time) {
+                    continue;
+                }
+                index.insert(point.time, points.len());
+                points.push(StitchedPoint {
+                    time: point.time,
+                    value: point.value.iter().map(|&v| f64::from(v) * scale).collect(),
+                    has_data: point.has_data.clone(),
+                    is_partial: point.is_partial,
+                    chunk: n,
+                });
+            }
+
+            provenance.push(Chunk {
+                start: chunk.timeline_data.first().map(|point| point.time),
+                end: chunk.timeline_data.last().map(|point| point.time),
+                scale,
+                overlap,
+                rescaled,
+            });
+        }
+
+        points.sort_by_key(|point| point.time);
+        normalize(&mut points);
+        Self {
+            points,
+            chunks: provenance,
+        }
+    }
+
+    /// Stitch consecutive curves sharing some points, rejecting the curves which can't be rescaled.
+    ///
+    /// Returns a `StitchedSeries` normalized to 0–100,
+    /// or an `Error::WindowWithoutOverlap` if a curve with interest shares no interest with the previous ones.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::search_interest::SearchInterestResponse;
+    /// # use rtrend::stitch::StitchedSeries;
+    /// # use rtrend::Error;
+    /// let first: SearchInterestResponse = serde_json::from_str(r#"{"timelineData": [
+    ///     {"time": "1609459200", "formattedTime": "Jan 1, 2021", "value": [100], "hasData": [true], "formattedValue": ["100"]},
+    ///     {"time": "1609545600", "formattedTime": "Jan 2, 2021", "value": [0], "hasData": [false], "formattedValue": ["0"]}
+    /// ]}"#).unwrap();
+    ///
+    /// // Jan 2 is shared but has no interest, the scale of the second window is unknown
+    /// let second: SearchInterestResponse = serde_json::from_str(r#"{"timelineData": [
+    ///     {"time": "1609545600", "formattedTime": "Jan 2, 2021", "value": [0], "hasData": [false], "formattedValue": ["0"]},
+    ///     {"time": "1609632000", "formattedTime": "Jan 3, 2021", "value": [100], "hasData": [true], "formattedValue": ["100"]}
+    /// ]}"#).unwrap();
+    ///
+    /// let series = StitchedSeries::stitch(&[first.clone(), second.clone()]);
+    /// assert!(series.chunks[0].rescaled);
+    /// assert!(!series.chunks[1].rescaled);
+    ///
+    /// let result = StitchedSeries::try_stitch(&[first, second]);
+    /// assert!(matches!(result, Err(Error::WindowWithoutOverlap(1))));
+    /// ```
+    pub fn try_stitch(chunks: &[SearchInterestResponse]) -> Result<Self> {
+        let series = Self::stitch(chunks);
+
+        match series.chunks.iter().position(|chunk| !chunk.rescaled) {
+            Some(window) => Err(Error::WindowWithoutOverlap(window)),
+            None => Ok(series),
+        }
+    }
+}
+
+// Highest point of the series becomes 100
+fn normalize(points: &mut [StitchedPoint]) {
+    let max = points
+        .iter()
+        .flat_map(|point| point.value.iter().copied())
+        .fold(0.0, f64::max);
+
+    if max > 0.0 {
+        for value in points.iter_mut().flat_map(|point| point.value.iter_mut()) {
+            *value = *value * 100.0 / max;
+        }
+    }
+}
+
+// Overlapping daily windows covering `start` to `end`, both included
+pub(crate) fn day_windows(start: NaiveDate, end: NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+    windows(
+        start,
+        end,
+        Duration::days(DAILY_WINDOW_DAYS - 1),
+        Duration::days(DAILY_WINDOW_DAYS - DAILY_OVERLAP_DAYS),
+    )
+}
+
+// Overlapping hourly windows covering `start` to `end`, both included
+pub(crate) fn hour_windows(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    windows(
+        start,
+        end,
+        Duration::hours(HOURLY_WINDOW_HOURS),
+        Duration::hours(HOURLY_WINDOW_HOURS - HOURLY_OVERLAP_HOURS),
+    )
+}
+
+// Windows of `span` starting every `step`, the last one ends on `end`
+fn windows<T>(start: T, end: T, span: Duration, step: Duration) -> Vec<(T, T)>
+where
+    T: Copy + Ord + Add<Duration, Output = T> + Sub<Duration, Output = T>,
+{
+    let mut windows = Vec::new();
+    let mut from = start;
+
+    loop {
+        let to = std::cmp::min(from + span, end);
+        windows.push((from, to));
+        if to >= end {
+            return windows;
+        }
+        from = from + step;
+    }
+}
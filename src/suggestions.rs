@@ -0,0 +1,129 @@
+//! Represent Google Trend keyword suggestions.
+//!
+//! Convert a free-text term into Knowledge Graph topics (`/m/...`), the titles and types are given in the langage of the client.
+
+use compact_str::CompactString;
+use serde::Deserialize;
+
+use crate::errors::Result;
+use crate::transport::Transport;
+use crate::ranked_list::Topic;
+use crate::request_handler::Query;
+#[cfg(feature = "async")]
+use crate::request_handler::AsyncQuery;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::Client;
+
+/// Topics answered by the autocomplete request, behind an anti JSON hijacking prefix.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AutocompleteResponse {
+	default: AutocompleteTopics
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AutocompleteTopics {
+	#[serde(default)]
+	topics: Vec<Topic>
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Suggestions<C = Client> {
+    pub client: C,
+    pub term: CompactString,
+}
+
+impl<C> Suggestions<Client<C>> {
+    /// Create a `Suggestions` instance for a free-text term.
+    ///
+    /// The client does not need keywords nor to be built.
+    ///
+    /// Returns a `Suggestions` instance
+    pub fn new(client: Client<C>, term: &str) -> Self {
+        Self {
+            client,
+            term: term.into(),
+        }
+    }
+}
+
+impl<T: Transport> Suggestions<Client<T>> {
+    /// Retrieve the topics matching the term.
+    ///
+    /// Returns a list of `Topic`, the most relevant first.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::{ClientBuilder, Lang, Suggestions};
+    /// # use rtrend::transport::Fixtures;
+    /// let body = r#")]}',
+    /// {"default": {"topics": [{"mid": "/m/0dsbpg6", "title": "Rust", "type": "Langage de programmation"}]}}"#;
+    /// let client = ClientBuilder::new()
+    ///     .with_lang(Lang::FR)
+    ///     .build_with(Fixtures::new().with("autocomplete/rust", body))
+    ///     .unwrap();
+    ///
+    /// for topic in Suggestions::new(client, "rust").get() {
+    ///     println!("{} - {} ({})", topic.mid, topic.title, topic.kind);
+    /// }
+    /// ```
+    ///
+    /// Topics can be used as keywords:
+    /// ```no_run
+    /// # use std::convert::TryFrom;
+    /// # use rtrend::{Country, Keywords, Keyword, Client, Suggestions, SearchInterest};
+    /// let client = Client::new(Keywords::default(), Country::ALL);
+    /// let topic = Suggestions::new(client.clone(), "rust").get().remove(0);
+    ///
+    /// let keywords = Keywords::try_from(vec![Keyword::from(topic)]).unwrap();
+    /// let search_interest = SearchInterest::new(client.with_keywords(keywords).build()).get();
+    /// ```
+    ///
+    /// # Panics
+    /// Panic if the request fails, see [`Suggestions::try_get`].
+    pub fn get(&self) -> Vec<Topic> {
+        self.try_get().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the topics matching the term without panicking.
+    ///
+    /// The term is sent as a path segment, its reserved characters are escaped.
+    ///
+    /// Returns a list of `Topic`, the most relevant first, or an `Error` if the request fails.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::{ClientBuilder, Suggestions};
+    /// # use rtrend::transport::Fixtures;
+    /// let body = r#")]}',
+    /// {"default": {"topics": [{"mid": "/m/07657k", "title": "C#", "type": "Programming language"}]}}"#;
+    /// let fixtures = Fixtures::new().with("autocomplete/c%23%20%2F%20.net", body);
+    /// let client = ClientBuilder::new().build_with(fixtures.clone()).unwrap();
+    ///
+    /// let topics = Suggestions::new(client, "c# / .net").try_get().unwrap();
+    ///
+    /// assert_eq!(topics[0].mid, "/m/07657k");
+    /// assert_eq!(fixtures.requests()[0].path(), "/trends/api/autocomplete/c%23%20%2F%20.net");
+    /// ```
+    pub fn try_get(&self) -> Result<Vec<Topic>> {
+        Ok(self.send_request(None)?.remove(0).default.topics)
+    }
+}
+
+#[cfg(feature = "async")]
+impl Suggestions<AsyncClient> {
+    /// Asynchronously retrieve the topics matching the term.
+    ///
+    /// # Panics
+    /// Panic if the request fails.
+    pub async fn get(&self) -> Vec<Topic> {
+        self.try_get()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve the topics matching the term without panicking.
+    pub async fn try_get(&self) -> Result<Vec<Topic>> {
+        Ok(self.send_request(None).await?.remove(0).default.topics)
+    }
+}
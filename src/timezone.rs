@@ -0,0 +1,97 @@
+//! Represent the timezone Google Trend uses to compute and display the timelines.
+//!
+//! A timezone is either a fixed offset or an IANA zone (`Europe/Paris`).
+//! Google Trend computes a whole timeline with a single offset, for an IANA zone the one in force at the end of the period.
+//! The points of the timeline carry that same offset, even when the period spans a daylight saving time change.
+
+use crate::TimeRange;
+use chrono::{DateTime, FixedOffset, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Create a new Timezone.
+///
+/// Returns a Timezone instance.
+///
+/// # Example
+/// ```
+/// # use rtrend::Timezone;
+/// # use chrono::{FixedOffset, TimeZone, Utc};
+/// let tokyo = Timezone::from(FixedOffset::east_opt(9 * 3600).unwrap());
+/// let paris = Timezone::from(chrono_tz::Europe::Paris);
+///
+/// let winter = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+/// let summer = Utc.with_ymd_and_hms(2021, 7, 1, 0, 0, 0).unwrap();
+///
+/// assert_eq!(tokyo.offset_at(winter).local_minus_utc(), 9 * 3600);
+/// assert_eq!(paris.offset_at(winter).local_minus_utc(), 3600);
+/// assert_eq!(paris.offset_at(summer).local_minus_utc(), 2 * 3600);
+/// ```
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Timezone {
+    Fixed(FixedOffset),
+    Iana(Tz),
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Timezone::Iana(Tz::UTC)
+    }
+}
+
+impl Timezone {
+    /// Offset in force at `time`.
+    pub fn offset_at(&self, time: DateTime<Utc>) -> FixedOffset {
+        match self {
+            Timezone::Fixed(offset) => *offset,
+            Timezone::Iana(tz) => tz.offset_from_utc_datetime(&time.naive_utc()).fix(),
+        }
+    }
+
+    /// Convert a time to this timezone.
+    pub fn convert(&self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        time.with_timezone(&self.offset_at(time))
+    }
+
+    /// Offset sent to Google Trend for a timeline on `period`, the one in force at the end of the period.
+    ///
+    /// Periods ending now, and periods which are not a valid [`TimeRange`], get the current offset.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::Timezone;
+    /// let paris = Timezone::from(chrono_tz::Europe::Paris);
+    ///
+    /// assert_eq!(paris.offset_for("2021-06-01 2021-12-31").local_minus_utc(), 3600);
+    /// assert_eq!(paris.offset_for("2021-01-01T00 2021-07-01T00").local_minus_utc(), 2 * 3600);
+    /// ```
+    pub fn offset_for(&self, period: &str) -> FixedOffset {
+        let end = period.parse::<TimeRange>().ok().and_then(|range| range.end());
+        self.offset_at(end.unwrap_or_else(Utc::now))
+    }
+
+    // Google Trend expects the offset in minutes west of UTC, UTC+2 is `-120`
+    pub(crate) fn param(&self) -> String {
+        offset_param(self.offset_at(Utc::now()))
+    }
+
+    // Offset of a timeline on `period`, see `offset_for`
+    pub(crate) fn param_for(&self, period: &str) -> String {
+        offset_param(self.offset_for(period))
+    }
+}
+
+fn offset_param(offset: FixedOffset) -> String {
+    (-offset.local_minus_utc() / 60).to_string()
+}
+
+impl From<FixedOffset> for Timezone {
+    fn from(offset: FixedOffset) -> Self {
+        Timezone::Fixed(offset)
+    }
+}
+
+impl From<Tz> for Timezone {
+    fn from(tz: Tz) -> Self {
+        Timezone::Iana(tz)
+    }
+}
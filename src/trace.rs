@@ -0,0 +1,58 @@
+//! Events emitted with the `tracing` feature, nothing is emitted without it.
+//!
+//! Widget tokens and cookies never appear in the events, urls go through [`redact`] and bodies through [`redact_tokens`].
+
+use reqwest::Url;
+
+pub(crate) const REDACTED: &str = "REDACTED";
+
+// Forward to the `tracing` macro of the same level, expand to nothing without the feature
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+/// Url with the widget token replaced by `REDACTED`.
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(crate) fn redact(url: &Url) -> String {
+    let mut redacted = url.clone();
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if key == "token" { REDACTED.into() } else { value };
+            (key.into_owned(), value.into_owned())
+        })
+        .collect();
+
+    if !query.is_empty() {
+        redacted.query_pairs_mut().clear().extend_pairs(query);
+    }
+    redacted.to_string()
+}
+
+// Widget tokens are sent as `"token":"APP6_UEAAAAA..."` in the explore response, they never hold escaped quotes
+pub(crate) fn redact_tokens(body: &str) -> String {
+    const KEY: &str = "\"token\"";
+    let mut redacted = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(start) = rest.find(KEY) {
+        let (before, after) = rest.split_at(start + KEY.len());
+        redacted.push_str(before);
+
+        let value = after.trim_start().strip_prefix(':').map(str::trim_start);
+        match value.and_then(|value| value.strip_prefix('"')) {
+            Some(value) => {
+                let end = value.find('"').unwrap_or(value.len());
+                redacted.push_str(":\"");
+                redacted.push_str(REDACTED);
+                rest = &value[end..];
+            }
+            None => rest = after,
+        }
+    }
+    redacted.push_str(rest);
+    redacted
+}
@@ -0,0 +1,174 @@
+//! HTTP layer used by the blocking client.
+//!
+//! Every request of a [`Client`](crate::Client) goes through a [`Transport`].
+//! `reqwest::blocking::Client` is the default one, [`Fixtures`] answers with recorded bodies
+//! so the parsing of every endpoint can be tested without network.
+
+use crate::errors::Result;
+use reqwest::header::HeaderMap;
+use reqwest::{StatusCode, Url};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Response of a [`Transport`].
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// A `200 OK` response with this body.
+    pub fn ok(body: &str) -> Self {
+        Self {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: body.to_string(),
+        }
+    }
+}
+
+/// Send the GET requests of a client.
+///
+/// A transport is cloned along with the client, clones have to share their state (connection pool, fixtures, ...).
+/// The client gives the headers of every request, the consent cookie among them.
+pub trait Transport: Clone {
+    fn get(&self, url: &Url, headers: &HeaderMap) -> Result<HttpResponse>;
+}
+
+impl Transport for reqwest::blocking::Client {
+    fn get(&self, url: &Url, headers: &HeaderMap) -> Result<HttpResponse> {
+        let response = reqwest::blocking::Client::get(self, url.clone())
+            .headers(headers.clone())
+            .send()?;
+        Ok(HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.text()?,
+        })
+    }
+}
+
+/// Transport answering with recorded responses, no request is sent.
+///
+/// A response is chosen by the path of the url, `explore` or `widgetdata/multiline` for example.
+/// When several responses are recorded for a path, they are given in order and the last one is repeated.
+/// Clones share the responses and the history of requests.
+///
+/// # Example
+/// ```
+/// # use rtrend::{ClientBuilder, Country, Keywords, SearchInterest, RegionInterest, RelatedQueries};
+/// # use rtrend::transport::Fixtures;
+/// let explore = r#")]}'{"widgets": [
+///     {"id": "TIMESERIES", "token": "t0", "request": {"comparisonItem": []}},
+///     {"id": "GEO_MAP", "token": "t1", "request": {"resolution": "COUNTRY", "comparisonItem": []}},
+///     {"id": "RELATED_QUERIES", "token": "t2",
+///      "request": {"restriction": {"complexKeywordsRestriction": {"keyword": [{"type": "BROAD", "value": "rust"}]}}}}
+/// ]}"#;
+/// let multiline = r#")]}',
+/// {"default": {"timelineData": [
+///     {"time": "1609459200", "formattedTime": "Jan 1, 2021", "value": [42], "hasData": [true], "formattedValue": ["42"]}
+/// ]}}"#;
+/// let comparedgeo = r#")]}',
+/// {"default": {"geoMapData": [
+///     {"coordinates": {"lat": 46.2, "lng": 2.2}, "formattedValue": ["100"], "geoName": "France",
+///      "hasData": [true], "maxValueIndex": 0, "value": [100]}
+/// ]}}"#;
+/// let relatedsearches = r#")]}',
+/// {"default": {"rankedList": [
+///     {"rankedKeyword": [{"query": "rust lang", "value": 100, "formattedValue": "100", "link": "/"}]},
+///     {"rankedKeyword": [{"query": "rust game", "value": 250, "formattedValue": "+250%", "link": "/"}]}
+/// ]}}"#;
+///
+/// let fixtures = Fixtures::new()
+///     .with("explore", explore)
+///     .with("widgetdata/multiline", multiline)
+///     .with("widgetdata/comparedgeo", comparedgeo)
+///     .with("widgetdata/relatedsearches", relatedsearches);
+///
+/// let client = ClientBuilder::new()
+///     .with_keywords(Keywords::new(vec!["rust"]))
+///     .build_with(fixtures.clone())
+///     .unwrap()
+///     .try_build()
+///     .unwrap();
+///
+/// let timeline = SearchInterest::new(client.clone()).try_get().unwrap();
+/// assert_eq!(timeline.timeline_data[0].value, vec![42]);
+///
+/// let regions = RegionInterest::new(client.clone()).try_get().unwrap();
+/// assert_eq!(regions[0].geo_name, "France");
+///
+/// let related = RelatedQueries::new(client).try_get_for("rust").unwrap();
+/// assert_eq!(related.rising[0].formatted_value, "+250%");
+///
+/// assert_eq!(fixtures.requests().len(), 4);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Fixtures {
+    responses: Arc<Mutex<Vec<Route>>>,
+    requests: Arc<Mutex<Vec<Url>>>,
+}
+
+// Responses recorded for the urls ending by a path
+type Route = (String, VecDeque<HttpResponse>);
+
+impl Fixtures {
+    /// Create a `Fixtures` instance without any response.
+    ///
+    /// Returns a `Fixtures` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a `200 OK` response for the urls ending by `path`.
+    ///
+    /// Returns a `Fixtures` instance.
+    pub fn with(self, path: &str, body: &str) -> Self {
+        self.with_response(path, HttpResponse::ok(body))
+    }
+
+    /// Record any response for the urls ending by `path`, to test errors for example.
+    ///
+    /// Returns a `Fixtures` instance.
+    pub fn with_response(self, path: &str, response: HttpResponse) -> Self {
+        {
+            let mut responses = self.responses.lock().unwrap();
+            match responses.iter_mut().find(|(recorded, _)| recorded == path) {
+                Some((_, queue)) => queue.push_back(response),
+                None => responses.push((path.to_string(), VecDeque::from(vec![response]))),
+            }
+        }
+        self
+    }
+
+    /// Urls requested so far, in order.
+    pub fn requests(&self) -> Vec<Url> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Urls without a recorded response get a `404 Not Found`.
+impl Transport for Fixtures {
+    fn get(&self, url: &Url, _headers: &HeaderMap) -> Result<HttpResponse> {
+        self.requests.lock().unwrap().push(url.clone());
+
+        let mut responses = self.responses.lock().unwrap();
+        let path = url.path().trim_end_matches('/');
+        let queue = responses
+            .iter_mut()
+            .find(|(recorded, _)| path.ends_with(recorded.trim_end_matches('/')))
+            .map(|(_, queue)| queue);
+
+        Ok(match queue {
+            Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+            Some(queue) => queue[0].clone(),
+            None => HttpResponse {
+                status: StatusCode::NOT_FOUND,
+                headers: HeaderMap::new(),
+                body: String::new(),
+            },
+        })
+    }
+}
@@ -0,0 +1,498 @@
+//! Compare more than 5 keywords on a common scale.
+//!
+//! Google Trend compares at most 5 keywords and normalizes every comparison on its own peak.
+//! A `KeywordUniverse` splits the keywords into batches of 5 sharing an anchor keyword.
+//! Each batch is requested on its own, then rescaled so the anchor gets the same interest in every batch.
+//! The interest over time of a batch is rescaled as a whole, the interest by region region by region,
+//! as Google gives there the share of each keyword among the searches of the region.
+//!
+//! Values are rounded by Google to integers. The `step` of each [`BatchReport`] is that rounding on the common scale,
+//! it grows when the anchor is much smaller than the other keywords of the batch.
+
+use crate::errors::{Error, Result};
+use crate::transport::Transport;
+use crate::region_interest::InterestForRegion;
+use crate::search_interest::SearchInterestResponse;
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::{Client, Keyword, Keywords, RegionInterest, SearchInterest};
+
+use chrono::{DateTime, FixedOffset};
+use compact_str::CompactString;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+// Keywords of a batch besides the anchor
+const BATCH_SIZE: usize = 4;
+
+/// Interest over time of every keyword of the universe.
+///
+/// # Example
+/// ```
+/// # use rtrend::search_interest::SearchInterestResponse;
+/// # use rtrend::universe::UniverseSeries;
+/// # use rtrend::Keywords;
+/// let first: SearchInterestResponse = serde_json::from_str(r#"{"timelineData": [
+///     {"time": "1609459200", "formattedTime": "Jan 1, 2021", "value": [50, 100], "hasData": [true, true], "formattedValue": ["50", "100"]}
+/// ]}"#).unwrap();
+/// let second: SearchInterestResponse = serde_json::from_str(r#"{"timelineData": [
+///     {"time": "1609459200", "formattedTime": "Jan 1, 2021", "value": [10, 100], "hasData": [true, true], "formattedValue": ["10", "100"]}
+/// ]}"#).unwrap();
+///
+/// // "apple" is the anchor of both batches
+/// let series = UniverseSeries::from_batches(vec![
+///     (Keywords::new(vec!["apple", "banana"]), first),
+///     (Keywords::new(vec!["apple", "cherry"]), second),
+/// ]).unwrap();
+///
+/// assert_eq!(series.anchor.value(), "apple");
+/// // "cherry" is 10 times more popular than "apple", twice as popular as "banana"
+/// assert_eq!(series.points[0].value, vec![10.0, 20.0, 100.0]);
+/// // One unit of the second batch is worth 1 on the common scale, against 0.2 for the first one
+/// assert_eq!(series.batches[1].step, 1.0);
+/// assert_eq!(series.batches[0].step, 0.2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UniverseSeries {
+	pub anchor: Keyword,
+	/// Keywords in the order of the values, the anchor first.
+	pub keywords: Vec<Keyword>,
+	pub points: Vec<UniversePoint>,
+	pub batches: Vec<BatchReport>,
+}
+
+/// A point of the universe curve, `value` holds one entry per keyword of the universe.
+#[derive(Clone, Debug)]
+pub struct UniversePoint {
+	pub time: DateTime<FixedOffset>,
+	pub value: Vec<f64>,
+}
+
+/// Interest by region of every keyword of the universe.
+#[derive(Clone, Debug)]
+pub struct UniverseRegions {
+	pub anchor: Keyword,
+	/// Keywords in the order of the values, the anchor first.
+	pub keywords: Vec<Keyword>,
+	pub regions: Vec<UniverseRegion>,
+	pub batches: Vec<BatchReport>,
+}
+
+/// Interest of every keyword of the universe in a region.
+#[derive(Clone, Debug)]
+pub struct UniverseRegion {
+	pub geo_name: CompactString,
+	pub value: Vec<f64>,
+}
+
+/// How a batch was put on the common scale.
+#[derive(Clone, Debug)]
+pub struct BatchReport {
+	/// Keywords of the batch, the anchor first.
+	pub keywords: Vec<Keyword>,
+	/// Factor applied to the batch to match the anchor of the first batch,
+	/// the largest one of the regions when they are rescaled one by one.
+	pub scale: f64,
+	/// Size of one Google unit of the batch on the common scale, the largest one of the regions when they are rescaled one by one.
+	/// Google rounds its values to the unit, a value of the batch is only known to ± `step / 2`.
+	pub step: f64,
+}
+
+impl UniverseSeries {
+    /// Put curves of batches sharing their first keyword (the anchor) on a common scale, normalized to 0–100.
+    ///
+    /// Returns an `Error::KeywordNotSet` if a batch does not start with the anchor
+    /// or an `Error::AnchorWithoutData` if the anchor has no interest in a batch.
+    pub fn from_batches(batches: Vec<(Keywords, SearchInterestResponse)>) -> Result<Self> {
+        let batches = batches
+            .into_iter()
+            .map(|(keywords, response)| {
+                let rows = response
+                    .timeline_data
+                    .into_iter()
+                    .map(|point| (point.time, point.value))
+                    .collect();
+                (keywords, rows)
+            })
+            .collect();
+
+        let combined = combine(batches, Rescale::Batch)?;
+        Ok(Self {
+            anchor: combined.anchor,
+            keywords: combined.keywords,
+            points: combined
+                .rows
+                .into_iter()
+                .map(|(time, value)| UniversePoint { time, value })
+                .collect(),
+            batches: combined.batches,
+        })
+    }
+}
+
+impl UniverseRegions {
+    /// Put regions of batches sharing their first keyword (the anchor) on a common scale, normalized to 0–100.
+    ///
+    /// Each region is rescaled on the interest of the anchor in this region.
+    ///
+    /// Returns an `Error::KeywordNotSet` if a batch does not start with the anchor
+    /// or an `Error::AnchorWithoutData` if the anchor has no interest in a batch,
+    /// or in a region where another keyword of the batch has some.
+    ///
+    /// # Example
+    /// ```
+    /// # use rtrend::region_interest::InterestForRegion;
+    /// # use rtrend::universe::UniverseRegions;
+    /// # use rtrend::Keywords;
+    /// let regions = |values: &str| -> Vec<InterestForRegion> { serde_json::from_str(values).unwrap() };
+    /// let region = |name: &str, value: [u8; 2]| format!(
+    ///     r#"{{"coordinates": {{"lat": 0, "lng": 0}}, "formattedValue": [], "geoName": "{}", "hasData": [true, true], "maxValueIndex": 0, "value": [{}, {}]}}"#,
+    ///     name, value[0], value[1]
+    /// );
+    ///
+    /// // Shares of the searches of each region
+    /// let first = regions(&format!("[{}, {}]", region("Spain", [50, 50]), region("France", [20, 80])));
+    /// let second = regions(&format!("[{}, {}]", region("Spain", [25, 75]), region("France", [20, 80])));
+    ///
+    /// let universe = UniverseRegions::from_batches(vec![
+    ///     (Keywords::new(vec!["apple", "banana"]), first),
+    ///     (Keywords::new(vec!["apple", "cherry"]), second),
+    /// ]).unwrap();
+    ///
+    /// // "cherry" is searched 3 times more than "apple" in Spain, 4 times more in France
+    /// let ratio = |n: usize| universe.regions[n].value[2] / universe.regions[n].value[0];
+    /// assert!((ratio(0) - 3.0).abs() < 1e-9);
+    /// assert!((ratio(1) - 4.0).abs() < 1e-9);
+    /// ```
+    pub fn from_batches(batches: Vec<(Keywords, Vec<InterestForRegion>)>) -> Result<Self> {
+        let batches = batches
+            .into_iter()
+            .map(|(keywords, regions)| {
+                let rows = regions
+                    .into_iter()
+                    .map(|region| (region.geo_name, region.value))
+                    .collect();
+                (keywords, rows)
+            })
+            .collect();
+
+        let combined = combine(batches, Rescale::Row)?;
+        Ok(Self {
+            anchor: combined.anchor,
+            keywords: combined.keywords,
+            regions: combined
+                .rows
+                .into_iter()
+                .map(|(geo_name, value)| UniverseRegion { geo_name, value })
+                .collect(),
+            batches: combined.batches,
+        })
+    }
+}
+
+/// Any number of keywords compared on a common scale.
+///
+/// The anchor is the first keyword unless set with [`KeywordUniverse::with_anchor`].
+/// Pick a keyword with a steady interest of the same magnitude as the others to keep the precision.
+#[derive(Clone, Debug)]
+pub struct KeywordUniverse<C = Client> {
+    pub client: C,
+    pub anchor: Keyword,
+    /// Keywords of the universe besides the anchor.
+    pub keywords: Vec<Keyword>,
+}
+
+impl<C> KeywordUniverse<Client<C>> {
+    /// Create a `KeywordUniverse` instance, the keywords of the client are replaced by each batch.
+    ///
+    /// Returns a `KeywordUniverse` instance.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use rtrend::{Client, Country, Keywords, KeywordUniverse};
+    /// let client = Client::new(Keywords::new(vec!["coca-cola"]), Country::US);
+    /// let brands = vec!["coca-cola", "pepsi", "fanta", "sprite", "dr pepper", "7up", "mountain dew"];
+    ///
+    /// let universe = KeywordUniverse::new(client, brands).search_interest();
+    ///
+    /// for point in universe.points {
+    ///     println!("{} {:?}", point.time, point.value);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if there is no keyword.
+    pub fn new<K: Into<Keyword>>(client: Client<C>, keywords: impl IntoIterator<Item = K>) -> Self {
+        Self::try_new(client, keywords).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Create a `KeywordUniverse` instance without panicking.
+    ///
+    /// Keywords are normalized like [`Keywords`], returns an `Error::KeywordMinCapacity` if there is no keyword.
+    pub fn try_new<K: Into<Keyword>>(
+        client: Client<C>,
+        keywords: impl IntoIterator<Item = K>,
+    ) -> Result<Self> {
+        let mut keywords = Keywords::from_iter(keywords).keywords;
+        if keywords.is_empty() {
+            return Err(Error::KeywordMinCapacity);
+        }
+        let anchor = keywords.remove(0);
+
+        Ok(Self {
+            client,
+            anchor,
+            keywords,
+        })
+    }
+
+    /// Set the anchor shared by every batch, it is added to the universe if needed.
+    ///
+    /// Returns a `KeywordUniverse` instance.
+    pub fn with_anchor(mut self, anchor: impl Into<Keyword>) -> Self {
+        let anchor = anchor.into();
+        self.keywords.retain(|key| !key.same_as(&anchor));
+
+        let previous = std::mem::replace(&mut self.anchor, anchor);
+        if !previous.same_as(&self.anchor) {
+            self.keywords.insert(0, previous);
+        }
+        self
+    }
+
+    /// Batches of up to 5 keywords, each one starting with the anchor.
+    pub fn batches(&self) -> Vec<Keywords> {
+        if self.keywords.is_empty() {
+            return vec![Keywords {
+                keywords: vec![self.anchor.clone()],
+            }];
+        }
+
+        self.keywords
+            .chunks(BATCH_SIZE)
+            .map(|chunk| {
+                let mut keywords = vec![self.anchor.clone()];
+                keywords.extend_from_slice(chunk);
+                Keywords { keywords }
+            })
+            .collect()
+    }
+}
+
+impl<T: Transport> KeywordUniverse<Client<T>> {
+    /// Retrieve the interest over time of every keyword on a common scale.
+    ///
+    /// # Panics
+    /// Panic if a request fails or if the anchor has no interest in a batch, see [`KeywordUniverse::try_search_interest`].
+    pub fn search_interest(&self) -> UniverseSeries {
+        self.try_search_interest()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the interest over time of every keyword on a common scale without panicking.
+    ///
+    /// Each batch is built with its own explore request.
+    pub fn try_search_interest(&self) -> Result<UniverseSeries> {
+        let mut responses = Vec::new();
+        for keywords in self.batches() {
+            let client = self.client.clone().with_keywords(keywords.clone()).try_build()?;
+            responses.push((keywords, SearchInterest::new(client).try_get()?));
+        }
+        UniverseSeries::from_batches(responses)
+    }
+
+    /// Retrieve the interest by region of every keyword on a common scale.
+    ///
+    /// # Panics
+    /// Panic if a request fails or if the anchor has no interest in a batch, see [`KeywordUniverse::try_region_interest`].
+    pub fn region_interest(&self) -> UniverseRegions {
+        self.try_region_interest()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Retrieve the interest by region of every keyword on a common scale without panicking.
+    ///
+    /// Each batch is built with its own explore request.
+    pub fn try_region_interest(&self) -> Result<UniverseRegions> {
+        let mut responses = Vec::new();
+        for keywords in self.batches() {
+            let client = self.client.clone().with_keywords(keywords.clone()).try_build()?;
+            responses.push((keywords, RegionInterest::new(client).try_get()?));
+        }
+        UniverseRegions::from_batches(responses)
+    }
+}
+
+#[cfg(feature = "async")]
+impl KeywordUniverse<AsyncClient> {
+    /// Asynchronously retrieve the interest over time of every keyword on a common scale.
+    ///
+    /// # Panics
+    /// Panic if a request fails or if the anchor has no interest in a batch.
+    pub async fn search_interest(&self) -> UniverseSeries {
+        self.try_search_interest()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve the interest over time of every keyword on a common scale without panicking.
+    pub async fn try_search_interest(&self) -> Result<UniverseSeries> {
+        let mut responses = Vec::new();
+        for keywords in self.batches() {
+            let client = self
+                .client
+                .clone()
+                .with_keywords(keywords.clone())
+                .try_build()
+                .await?;
+            responses.push((keywords, SearchInterest::new(client).try_get().await?));
+        }
+        UniverseSeries::from_batches(responses)
+    }
+
+    /// Asynchronously retrieve the interest by region of every keyword on a common scale.
+    ///
+    /// # Panics
+    /// Panic if a request fails or if the anchor has no interest in a batch.
+    pub async fn region_interest(&self) -> UniverseRegions {
+        self.try_region_interest()
+            .await
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Asynchronously retrieve the interest by region of every keyword on a common scale without panicking.
+    pub async fn try_region_interest(&self) -> Result<UniverseRegions> {
+        let mut responses = Vec::new();
+        for keywords in self.batches() {
+            let client = self
+                .client
+                .clone()
+                .with_keywords(keywords.clone())
+                .try_build()
+                .await?;
+            responses.push((keywords, RegionInterest::new(client).try_get().await?));
+        }
+        UniverseRegions::from_batches(responses)
+    }
+}
+
+// Rows (points or regions) of a batch with the raw values of its keywords
+type Batch<K> = (Keywords, Vec<(K, Vec<u8>)>);
+
+// Rows of every batch on the common scale
+struct Combined<K> {
+    anchor: Keyword,
+    keywords: Vec<Keyword>,
+    rows: Vec<(K, Vec<f64>)>,
+    batches: Vec<BatchReport>,
+}
+
+// How the rows of a batch are put on the scale of the first batch
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Rescale {
+    // The points of a batch share its peak, one factor for the whole batch
+    Batch,
+    // A region gives the share of each keyword among its searches, one factor per region
+    Row,
+}
+
+// Rescale batches of rows (points or regions) on the anchor of the first batch, then normalize them to 0–100
+fn combine<K: Clone + Eq + Hash>(batches: Vec<Batch<K>>, rescale: Rescale) -> Result<Combined<K>> {
+    let anchor = batches
+        .first()
+        .and_then(|(keywords, _)| keywords.keywords.first())
+        .cloned()
+        .ok_or(Error::KeywordMinCapacity)?;
+
+    let anchor_value = |value: &[u8]| value.first().map_or(0.0, |&v| f64::from(v));
+    let anchor_sum = |rows: &[(K, Vec<u8>)]| -> f64 { rows.iter().map(|(_, value)| anchor_value(value)).sum() };
+    let reference = anchor_sum(&batches[0].1);
+    let reference_rows: HashMap<K, f64> = batches[0]
+        .1
+        .iter()
+        .map(|(key, value)| (key.clone(), anchor_value(value)))
+        .collect();
+    let without_data = || Error::AnchorWithoutData(anchor.value().to_string());
+
+    let mut keywords = vec![anchor.clone()];
+    let mut rows: Vec<(K, Vec<f64>)> = Vec::new();
+    let mut index: HashMap<K, usize> = HashMap::new();
+    let mut reports = Vec::new();
+
+    for (n, (batch, batch_rows)) in batches.iter().enumerate() {
+        if !batch.keywords.first().is_some_and(|key| key.same_as(&anchor)) {
+            return Err(Error::KeywordNotSet(anchor.value().to_string()));
+        }
+
+        let sum = anchor_sum(batch_rows);
+        if reference <= 0.0 || sum <= 0.0 {
+            return Err(without_data());
+        }
+
+        // The anchor is taken from the first batch, the other batches only add their own keywords
+        let offset = keywords.len();
+        let skip = if n == 0 { 0 } else { 1 };
+        keywords.extend(batch.keywords.iter().skip(1).cloned());
+
+        let mut largest = 0.0;
+        for (key, value) in batch_rows {
+            let scale = match rescale {
+                Rescale::Batch => reference / sum,
+                Rescale::Row => {
+                    let reference = reference_rows.get(key).copied().unwrap_or(0.0);
+                    let own = anchor_value(value);
+                    if reference > 0.0 && own > 0.0 {
+                        reference / own
+                    } else if value.iter().skip(skip).all(|&v| v == 0) {
+                        // Nothing to rescale, the region has no interest for the keywords of the batch
+                        0.0
+                    } else {
+                        return Err(without_data());
+                    }
+                }
+            };
+            largest = f64::max(largest, scale);
+
+            let i = *index.entry(key.clone()).or_insert_with(|| {
+                rows.push((key.clone(), Vec::new()));
+                rows.len() - 1
+            });
+            let row = &mut rows[i].1;
+            let start = if n == 0 { 0 } else { offset };
+            row.resize(start, 0.0);
+            row.extend(value.iter().skip(skip).map(|&v| f64::from(v) * scale));
+        }
+
+        // Google rounds to the unit, one unit of the batch is worth its factor on the common scale
+        reports.push(BatchReport {
+            keywords: batch.keywords.clone(),
+            scale: largest,
+            step: largest,
+        });
+    }
+
+    let max = rows
+        .iter()
+        .flat_map(|(_, value)| value.iter().copied())
+        .fold(0.0, f64::max);
+    let factor = if max > 0.0 { 100.0 / max } else { 1.0 };
+
+    for (_, value) in rows.iter_mut() {
+        value.resize(keywords.len(), 0.0);
+        for v in value.iter_mut() {
+            *v *= factor;
+        }
+    }
+    // The scale matches the first batch, the step is measured once normalized
+    for report in reports.iter_mut() {
+        report.step *= factor;
+    }
+
+    Ok(Combined {
+        anchor,
+        keywords,
+        rows,
+        batches: reports,
+    })
+}
@@ -1,7 +1,109 @@
-pub fn sanitize_response(body: &str, pos: usize) -> &str {
-    let mut chars = body.chars();
-    for _ in 0..pos {
-        chars.next();
+use crate::errors::{Error, Result};
+use crate::trace;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
+// Anti JSON hijacking prefixes put by Google before its JSON, `)]}'` is followed by `,` on the widgets
+const XSSI_PREFIXES: [&str; 4] = [")]}'", ")]}", "while(1);", "for(;;);"];
+const EXCERPT_LENGTH: usize = 200;
+
+// Strip the anti JSON hijacking prefix, whatever it is
+pub fn strip_xssi(body: &str) -> &str {
+    let body = body.trim_start_matches('\u{feff}').trim_start();
+    let body = XSSI_PREFIXES
+        .iter()
+        .find_map(|prefix| body.strip_prefix(prefix))
+        .unwrap_or(body);
+    body.trim_start_matches(|c: char| c == ',' || c.is_whitespace())
+}
+
+// Beginning of a body for the errors, without the widget tokens
+pub fn excerpt(body: &str) -> String {
+    let body = trace::redact_tokens(body.trim());
+    match body.char_indices().nth(EXCERPT_LENGTH) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body,
+    }
+}
+
+// The sorry page of Google, shown to the addresses sending automated traffic
+fn is_captcha(body: &str) -> bool {
+    let body = body.to_ascii_lowercase();
+    body.contains("captcha") || body.contains("/sorry/") || body.contains("unusual traffic")
+}
+
+fn is_html(body: &str) -> bool {
+    body.trim_start().starts_with('<')
+}
+
+pub fn check_response(status: StatusCode, body: &str) -> Result<()> {
+    match status {
+        _ if is_html(body) && is_captcha(body) => Err(Error::Captcha {
+            excerpt: excerpt(body),
+        }),
+        StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited {
+            status,
+            excerpt: excerpt(body),
+        }),
+        status if !status.is_success() => Err(Error::HttpStatus {
+            status,
+            excerpt: excerpt(body),
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub fn parse_response<T: DeserializeOwned>(body: &str) -> Result<T> {
+    if is_html(body) {
+        return Err(if is_captcha(body) {
+            Error::Captcha { excerpt: excerpt(body) }
+        } else {
+            Error::HtmlPage { excerpt: excerpt(body) }
+        });
+    }
+
+    serde_json::from_str(strip_xssi(body)).map_err(|error| {
+        trace_event!(warn, error = %error, excerpt = %excerpt(body), "the response can't be parsed");
+        Error::InvalidJson {
+            error,
+            excerpt: excerpt(body),
+        }
+    })
+}
+
+// (De)serialize a unix timestamp sent as a string, like `"1609459200"`, the time is read as UTC
+pub mod timestamp {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, Tz: TimeZone>(time: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&time.timestamp().to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<DateTime<Utc>>>(deserializer: D) -> Result<T, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        let seconds: i64 = raw.parse().map_err(de::Error::custom)?;
+
+        Utc.timestamp_opt(seconds, 0)
+            .single()
+            .map(T::from)
+            .ok_or_else(|| de::Error::custom(format!("invalid timestamp {}", seconds)))
+    }
+}
+
+// (De)serialize a date sent as `"20240101"`
+pub mod compact_date {
+    use chrono::NaiveDate;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%Y%m%d";
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        NaiveDate::parse_from_str(&raw, FORMAT).map_err(de::Error::custom)
     }
-    chars.as_str()
 }